@@ -0,0 +1,25 @@
+//! Arbitrary bytes as layers of in-memory YAML, split at NUL bytes with the
+//! first piece as the base. Parse errors are fine; panics, including the
+//! collector's debug assertions on its frame stack, are not.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pointless_pointer::PointlessPointer;
+
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    let sources = text
+        .split('\0')
+        .enumerate()
+        .map(|(index, layer)| (format!("layer{index}"), layer.to_string()))
+        .collect();
+    let analyzer = PointlessPointer::from_sources(sources);
+    if let Ok(findings) = analyzer.findings() {
+        for finding in findings {
+            let _ = finding.to_string();
+        }
+    }
+    let _ = analyzer.collect();
+    let _ = analyzer.merged();
+});
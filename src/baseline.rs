@@ -0,0 +1,116 @@
+//! Acknowledging known findings so only new ones are reported.
+
+use crate::Finding;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+const BASELINE_VERSION: u32 = 1;
+
+/// One acknowledged finding. Line numbers are left out on purpose so moving
+/// an override around doesn't turn it into a new finding.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub kind: String,
+    pub file: String,
+    pub path: Vec<String>,
+    pub value: String,
+}
+
+impl BaselineEntry {
+    /// The entry for a finding that can fail a run; `None` for the rest,
+    /// which a baseline has no need to list. A deep path has no value of its
+    /// own, and a type change to a collection is recorded by the type.
+    fn for_finding(finding: &Finding) -> Option<Self> {
+        let (kind, value) = match finding {
+            Finding::PointlessOverride(override_item) => {
+                ("pointless-override", override_item.value.clone())
+            }
+            Finding::DuplicateKey(warning) => ("duplicate-key", warning.second_value.clone()),
+            Finding::DeepPath(_) => ("deep-path", String::new()),
+            Finding::TypeMismatch(warning) => (
+                "type-mismatch",
+                warning
+                    .value
+                    .clone()
+                    .unwrap_or_else(|| warning.value_type.to_string()),
+            ),
+            Finding::NullEmpty(warning) => ("null-empty", warning.value.clone()),
+            Finding::Conflict(warning) => ("conflict", warning.value.clone()),
+            Finding::EffectiveOverride(_)
+            | Finding::RedundantFile(_)
+            | Finding::RedundantSubtree(_)
+            | Finding::EmptyFile(_)
+            | Finding::FileStats(_)
+            | Finding::Provenance(_)
+            | Finding::DeadKey(_)
+            | Finding::SkippedFile(_) => return None,
+        };
+        Some(Self {
+            kind: kind.to_string(),
+            file: finding.file().to_string(),
+            path: finding.path().to_vec(),
+            value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    version: u32,
+    findings: BTreeSet<BaselineEntry>,
+}
+
+impl Baseline {
+    /// A baseline of every finding that can fail a run.
+    pub fn from_findings(findings: &[Finding]) -> Self {
+        let findings = findings
+            .iter()
+            .filter_map(BaselineEntry::for_finding)
+            .collect();
+        Self {
+            version: BASELINE_VERSION,
+            findings,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        let baseline: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid baseline {}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            bail!(
+                "Unsupported baseline version {} in {}",
+                baseline.version,
+                path.display()
+            );
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content + "\n")
+            .with_context(|| format!("Failed to write baseline {}", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Drop findings already in the baseline, returning how many were dropped.
+    pub fn apply(&self, findings: &mut Vec<Finding>) -> usize {
+        let before = findings.len();
+        findings.retain(|finding| {
+            BaselineEntry::for_finding(finding).is_none_or(|entry| !self.findings.contains(&entry))
+        });
+        before - findings.len()
+    }
+}
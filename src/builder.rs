@@ -0,0 +1,131 @@
+//! A way to set up a [`PointlessPointer`] that names each layer and option,
+//! rather than passing all the layers to [`PointlessPointer::new`] at once.
+
+use crate::{AnalyzeError, KeyNormalizer, PointlessPointer, Result, ValueNormalizer};
+use std::path::PathBuf;
+
+/// Builds a [`PointlessPointer`]. Only the base file is required. Each
+/// option left unset keeps the default of [`PointlessPointer::new`]:
+///
+/// - no defaults file and no override files, so the base is the only layer;
+/// - no paths ignored;
+/// - keys matched case-sensitively;
+/// - values compared by their YAML type, so `1.0` equals `1` and `yes`
+///   equals `true`;
+/// - later layers win over earlier ones;
+/// - no normalizers besides the built-in ones.
+///
+/// Options without a method here are set on the built analyzer with its
+/// method of the same name.
+///
+/// ```
+/// use pointless_pointer::PointlessPointer;
+///
+/// let analyzer = PointlessPointer::builder()
+///     .base("values.yaml")
+///     .override_file("staging.yaml")
+///     .override_file("prod.yaml")
+///     .ignore_path("image.tag")
+///     .case_insensitive(true)
+///     .build()
+///     .unwrap()
+///     .show_chain(true);
+/// assert_eq!(analyzer.labels(), ["values.yaml", "staging.yaml", "prod.yaml"]);
+/// ```
+#[derive(Default)]
+pub struct PointlessPointerBuilder {
+    base_file: Option<PathBuf>,
+    defaults_file: Option<PathBuf>,
+    override_files: Vec<PathBuf>,
+    ignore: Vec<String>,
+    case_insensitive: bool,
+    strict_strings: bool,
+    reverse_precedence: bool,
+    key_normalizer: Option<Box<dyn KeyNormalizer>>,
+    value_normalizer: Option<Box<dyn ValueNormalizer>>,
+}
+
+impl PointlessPointerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The base values file: `-` reads stdin, and an `http(s)://` URL is
+    /// fetched.
+    pub fn base(mut self, path: impl Into<PathBuf>) -> Self {
+        self.base_file = Some(path.into());
+        self
+    }
+
+    /// See [`PointlessPointer::defaults`].
+    pub fn defaults(mut self, path: impl Into<PathBuf>) -> Self {
+        self.defaults_file = Some(path.into());
+        self
+    }
+
+    /// Add an override file above the ones added so far.
+    pub fn override_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.override_files.push(path.into());
+        self
+    }
+
+    /// Add override files above the ones added so far, later ones winning.
+    pub fn override_files(mut self, paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        self.override_files
+            .extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Leave out findings at paths matching `pattern`; see
+    /// [`PointlessPointer::ignore_paths`]. Patterns are checked by
+    /// [`build`](Self::build).
+    pub fn ignore_path(mut self, pattern: impl Into<String>) -> Self {
+        self.ignore.push(pattern.into());
+        self
+    }
+
+    /// See [`PointlessPointer::case_insensitive_keys`].
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// See [`PointlessPointer::strict_strings`].
+    pub fn strict_strings(mut self, strict: bool) -> Self {
+        self.strict_strings = strict;
+        self
+    }
+
+    /// See [`PointlessPointer::reverse_precedence`].
+    pub fn reverse_precedence(mut self, reverse: bool) -> Self {
+        self.reverse_precedence = reverse;
+        self
+    }
+
+    /// See [`PointlessPointer::key_normalizer`].
+    pub fn key_normalizer(mut self, normalizer: impl KeyNormalizer + 'static) -> Self {
+        self.key_normalizer = Some(Box::new(normalizer));
+        self
+    }
+
+    /// See [`PointlessPointer::value_normalizer`].
+    pub fn value_normalizer(mut self, normalizer: impl ValueNormalizer + 'static) -> Self {
+        self.value_normalizer = Some(Box::new(normalizer));
+        self
+    }
+
+    /// The analyzer, or an error when no base file was given or an ignore
+    /// pattern doesn't parse.
+    pub fn build(self) -> Result<PointlessPointer> {
+        let base_file = self.base_file.ok_or(AnalyzeError::NoBase)?;
+        let mut analyzer = PointlessPointer::new(base_file, self.override_files)
+            .defaults(self.defaults_file)
+            .ignore_paths(&self.ignore)?
+            .case_insensitive_keys(self.case_insensitive)
+            .strict_strings(self.strict_strings)
+            .reverse_precedence(self.reverse_precedence);
+        analyzer.key_normalizer = self.key_normalizer;
+        analyzer.value_normalizer = self.value_normalizer;
+        Ok(analyzer)
+    }
+}
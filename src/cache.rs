@@ -0,0 +1,89 @@
+//! On-disk cache of parsed layers, keyed by a hash of their content so an
+//! edited file simply misses. The cache is best-effort: entries that can't be
+//! read or written are parsed again rather than failing the run.
+
+use crate::{Entries, InputFormat, SourceFile};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+pub(crate) struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The entry for a source. Values carry the label, and their layout
+    /// can change between versions, so both are part of the key, along with
+    /// the format the content was parsed as. The key outlives the process,
+    /// so it is a SHA-256 rather than the standard library's hash, which may
+    /// change from one Rust release to the next.
+    fn entry(&self, source: &SourceFile, format: InputFormat) -> PathBuf {
+        let mut hasher = Sha256::new();
+        let format = format!("{format:?}");
+        for part in [
+            env!("CARGO_PKG_VERSION"),
+            &source.label,
+            &format,
+            &source.content,
+        ] {
+            // Length first, so that no two lists of parts hash alike
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+        let digest: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        self.dir.join(format!("{digest}.json"))
+    }
+
+    pub(crate) fn load(&self, source: &SourceFile, format: InputFormat) -> Option<Entries> {
+        let cached = fs::read(self.entry(source, format)).ok()?;
+        serde_json::from_slice(&cached).ok()
+    }
+
+    pub(crate) fn store(&self, source: &SourceFile, format: InputFormat, values: &Entries) {
+        let Ok(serialized) = serde_json::to_vec(values) else {
+            return;
+        };
+        let _ = fs::create_dir_all(&self.dir);
+        let _ = fs::write(self.entry(source, format), serialized);
+    }
+}
+
+/// Serde for the parser's `ScalarStyle`, which doesn't implement it.
+pub(crate) mod scalar_style {
+    use saphyr_parser::ScalarStyle;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        style: &ScalarStyle,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let name = match style {
+            ScalarStyle::Plain => "plain",
+            ScalarStyle::SingleQuoted => "single",
+            ScalarStyle::DoubleQuoted => "double",
+            ScalarStyle::Literal => "literal",
+            ScalarStyle::Folded => "folded",
+        };
+        serializer.serialize_str(name)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ScalarStyle, D::Error> {
+        Ok(match String::deserialize(deserializer)?.as_str() {
+            "single" => ScalarStyle::SingleQuoted,
+            "double" => ScalarStyle::DoubleQuoted,
+            "literal" => ScalarStyle::Literal,
+            "folded" => ScalarStyle::Folded,
+            _ => ScalarStyle::Plain,
+        })
+    }
+}
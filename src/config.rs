@@ -0,0 +1,62 @@
+//! Settings read from `pointless_pointer.toml`, which the command line
+//! overrides.
+
+use crate::OutputFormat;
+use anyhow::{Context, Result};
+use pointless_pointer::is_url;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Looked up in the current directory when `--config` isn't given.
+pub const DEFAULT_CONFIG: &str = "pointless_pointer.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub base: Option<PathBuf>,
+    /// Override files or glob patterns, in precedence order
+    #[serde(default)]
+    pub overrides: Vec<PathBuf>,
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Value classes, written as for `--equivalent`
+    #[serde(default)]
+    pub equivalent: Vec<String>,
+    pub format: Option<OutputFormat>,
+    pub fail_on_findings: Option<bool>,
+    pub fail_on_warnings: Option<bool>,
+}
+
+impl Config {
+    /// Load the given config file, or the default one when it exists. Only a
+    /// config file that was asked for explicitly has to exist.
+    pub fn find(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => Self::load(path),
+            None if Path::new(DEFAULT_CONFIG).is_file() => Self::load(Path::new(DEFAULT_CONFIG)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Parse a config file. Relative paths in it are taken relative to the
+    /// file's own directory.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        let mut config: Self = toml::from_str(&content)
+            .with_context(|| format!("Invalid config {}", path.display()))?;
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let resolve = |file: &PathBuf| {
+            if file.as_os_str() == "-" || is_url(file) {
+                file.clone()
+            } else {
+                dir.join(file)
+            }
+        };
+        config.base = config.base.as_ref().map(resolve);
+        config.overrides = config.overrides.iter().map(resolve).collect();
+        Ok(config)
+    }
+}
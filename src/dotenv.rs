@@ -0,0 +1,108 @@
+//! Flat `KEY=VALUE` files, as loaded by dotenv libraries and
+//! `docker --env-file`. A key's `__` separators stand for the nesting of the
+//! YAML it mirrors, so `DB__HOST=db` is the value at `DB.HOST`.
+
+use crate::{Entries, ValueType, ValueWithLocation, normalize};
+use saphyr_parser::{Marker, ScalarStyle, ScanError};
+
+/// Separator between the segments of a key's path.
+const SEPARATOR: &str = "__";
+
+/// Every assignment in `content`. Blank lines, `#` comments and an `export `
+/// prefix are skipped; values may be single-quoted (taken literally),
+/// double-quoted (with `\n`, `\t`, `\"` and `\\` escapes) or bare, where a
+/// `#` after whitespace starts a comment.
+pub(crate) fn parse(file: &str, content: &str) -> Result<Entries, ScanError> {
+    let mut values = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let marker = |offset: usize| {
+            let column = line[..offset].chars().count();
+            Marker::new(offset, line_number, column)
+        };
+
+        let statement = line.trim_start();
+        if statement.is_empty() || statement.starts_with('#') {
+            continue;
+        }
+        let statement = statement
+            .strip_prefix("export ")
+            .map_or(statement, str::trim_start);
+        let key_offset = line.len() - statement.len();
+        let Some((key, rest)) = statement.split_once('=') else {
+            return Err(ScanError::new_str(
+                marker(key_offset),
+                "expected `KEY=VALUE`",
+            ));
+        };
+        let key = key.trim_end();
+        if key.is_empty() {
+            return Err(ScanError::new_str(
+                marker(key_offset),
+                "missing key before `=`",
+            ));
+        }
+
+        let value_offset = line.len() - rest.trim_start().len();
+        let (value, style, length) = parse_value(rest.trim_start())
+            .ok_or_else(|| ScanError::new_str(marker(value_offset), "unterminated quoted value"))?;
+        let mut location = ValueWithLocation::new(
+            value,
+            file.to_string(),
+            marker(value_offset),
+            marker(value_offset + length),
+        );
+        location.set_key(Some(marker(key_offset)));
+        location.style = style;
+        // Unlike YAML, `KEY=` is an empty string rather than null
+        if !location.value.is_empty() {
+            location.is_null = normalize::is_null(&location.value, style, None);
+            location.value_type = normalize::value_type(&location.value, style, None);
+        }
+
+        let path: Vec<String> = key.split(SEPARATOR).map(str::to_string).collect();
+        location.parents = vec![ValueType::Mapping; path.len()];
+        values.push((path, location));
+    }
+    Ok(values)
+}
+
+/// The value, how it was quoted, and the length in bytes of the text it
+/// was read from. `None` for a quote that is never closed.
+fn parse_value(text: &str) -> Option<(String, ScalarStyle, usize)> {
+    if let Some(quoted) = text.strip_prefix('\'') {
+        let end = quoted.find('\'')?;
+        return Some((
+            quoted[..end].to_string(),
+            ScalarStyle::SingleQuoted,
+            end + 2,
+        ));
+    }
+    if let Some(quoted) = text.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = quoted.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => return Some((value, ScalarStyle::DoubleQuoted, index + 2)),
+                '\\' => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    escaped @ ('"' | '\\') => value.push(escaped),
+                    other => {
+                        value.push('\\');
+                        value.push(other);
+                    }
+                },
+                _ => value.push(c),
+            }
+        }
+        return None;
+    }
+
+    let end = text
+        .char_indices()
+        .find(|&(index, c)| c == '#' && text[..index].ends_with(char::is_whitespace))
+        .map_or(text.len(), |(index, _)| index);
+    let value = text[..end].trim_end();
+    Some((value.to_string(), ScalarStyle::Plain, value.len()))
+}
@@ -0,0 +1,46 @@
+//! Expansion of `${VAR}` placeholders from the process environment.
+
+use std::collections::BTreeSet;
+use std::env;
+
+/// `raw` with every `${VAR}` replaced by the variable's value, or `None` if it
+/// has no placeholders. Unset variables are left as written and their names
+/// added to `unset`.
+pub(crate) fn expand(raw: &str, unset: &mut BTreeSet<String>) -> Option<String> {
+    if !raw.contains("${") {
+        return None;
+    }
+
+    let mut expanded = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let name = after
+            .find('}')
+            .map(|end| &after[..end])
+            .filter(|name| is_variable_name(name));
+        let Some(name) = name else {
+            // Not a placeholder; keep the `${` and carry on after it
+            expanded.push_str("${");
+            rest = after;
+            continue;
+        };
+        match env::var(name) {
+            Ok(value) => expanded.push_str(&value),
+            Err(_) => {
+                unset.insert(name.to_string());
+                expanded.push_str(&rest[start..start + name.len() + 3]);
+            }
+        }
+        rest = &after[name.len() + 1..];
+    }
+    expanded.push_str(rest);
+    Some(expanded)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,120 @@
+//! Why an analysis couldn't be completed.
+
+use saphyr_parser::ScanError;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum AnalyzeError {
+    /// A layer couldn't be read
+    Io { path: PathBuf, source: io::Error },
+    /// A layer couldn't be fetched over HTTP(S)
+    Fetch { url: String, source: ureq::Error },
+    /// The server answered a fetch with something other than success
+    HttpStatus { url: String, status: u16 },
+    /// A layer isn't valid YAML (or `KEY=VALUE` lines, for a dotenv layer)
+    Parse {
+        file: String,
+        line: usize,
+        column: usize,
+        source: ScanError,
+    },
+    /// A [`set_values`](crate::PointlessPointer::set_values) expression
+    /// doesn't parse
+    Set { expression: String, reason: String },
+    /// `-` was given for more than one layer
+    StdinReused,
+    /// A dotted-path glob given to the builder doesn't parse
+    Pattern {
+        pattern: String,
+        source: glob::PatternError,
+    },
+    /// An [`ignore_paths`](crate::PointlessPointer::ignore_paths) pattern is
+    /// neither a valid glob nor a valid canonical path
+    Ignore { pattern: String, reason: String },
+    /// [`build`](crate::PointlessPointerBuilder::build) was called without a
+    /// base file
+    NoBase,
+    /// The worker threads for [`jobs`](crate::PointlessPointer::jobs) couldn't
+    /// be started
+    ThreadPool(rayon::ThreadPoolBuildError),
+}
+
+impl AnalyzeError {
+    pub(crate) fn io(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        move |source| AnalyzeError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub(crate) fn parse(file: &str) -> impl FnOnce(ScanError) -> Self {
+        move |source| AnalyzeError::Parse {
+            file: file.to_string(),
+            line: source.marker().line(),
+            column: source.marker().col() + 1,
+            source,
+        }
+    }
+}
+
+impl fmt::Display for AnalyzeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalyzeError::Io { path, .. } => write!(f, "Failed to read {}", path.display()),
+            AnalyzeError::Fetch { url, .. } => write!(f, "Failed to fetch {url}"),
+            AnalyzeError::HttpStatus { url, status } => {
+                write!(f, "Failed to fetch {url}: HTTP {status}")
+            }
+            // The location is already in front, so only the scanner's
+            // description follows it rather than its whole message
+            AnalyzeError::Parse {
+                file, line, source, ..
+            } => {
+                write!(f, "{file}:{line}: {}", source.info())?;
+                if source.info().contains("tab") {
+                    write!(f, "; YAML must be indented with spaces, not tabs")?;
+                }
+                Ok(())
+            }
+            AnalyzeError::Set { expression, reason } => {
+                write!(f, "Invalid --set expression `{expression}`: {reason}")
+            }
+            AnalyzeError::StdinReused => write!(
+                f,
+                "`-` (stdin) can only be used once across the defaults, base and override files"
+            ),
+            AnalyzeError::Pattern { pattern, .. } => write!(f, "Invalid path pattern: {pattern}"),
+            AnalyzeError::Ignore { pattern, reason } => {
+                write!(f, "Invalid ignore pattern `{pattern}`: {reason}")
+            }
+            AnalyzeError::NoBase => write!(f, "No base file was given"),
+            AnalyzeError::ThreadPool(_) => write!(f, "Failed to start worker threads"),
+        }
+    }
+}
+
+impl Error for AnalyzeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnalyzeError::Io { source, .. } => Some(source),
+            AnalyzeError::Fetch { source, .. } => Some(source),
+            AnalyzeError::Pattern { source, .. } => Some(source),
+            AnalyzeError::ThreadPool(source) => Some(source),
+            AnalyzeError::HttpStatus { .. }
+            | AnalyzeError::Parse { .. }
+            | AnalyzeError::Set { .. }
+            | AnalyzeError::StdinReused
+            | AnalyzeError::Ignore { .. }
+            | AnalyzeError::NoBase => None,
+        }
+    }
+}
+
+impl From<rayon::ThreadPoolBuildError> for AnalyzeError {
+    fn from(source: rayon::ThreadPoolBuildError) -> Self {
+        AnalyzeError::ThreadPool(source)
+    }
+}
@@ -0,0 +1,238 @@
+//! Rewriting override files to drop pointless overrides.
+
+use crate::{
+    InputFormat, Override, STDIN_LABEL, SourceFile, ValueType, ValueWithLocation, is_url,
+    parse_source,
+};
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Lines to delete from one override file.
+#[derive(Debug, Clone)]
+pub struct FileFix {
+    pub file: String,
+    /// 1-based line numbers
+    pub removed_lines: BTreeSet<usize>,
+}
+
+impl FileFix {
+    /// `content` without the removed lines, keeping line endings intact.
+    pub fn apply_to(&self, content: &str) -> String {
+        content
+            .split_inclusive('\n')
+            .enumerate()
+            .filter(|(index, _)| !self.removed_lines.contains(&(index + 1)))
+            .map(|(_, line)| line)
+            .collect()
+    }
+
+    /// Rewrite the file on disk.
+    pub fn write(&self, source: &SourceFile) -> Result<()> {
+        fs::write(&self.file, self.apply_to(&source.content))?;
+        Ok(())
+    }
+}
+
+/// Work out which lines to delete for every cross-file pointless override.
+///
+/// Overrides are left alone when removing their lines would also take out
+/// something else: values sharing a line (flow style), values inside a
+/// sequence item, and values expanded from an alias. A sequence is only
+/// pointless as a whole, and dropping part of an item would change the list
+/// rather than fall back to the lower layer's. A parent mapping whose keys
+/// all go is removed along with them.
+pub fn plan_fixes(
+    sources: &[SourceFile],
+    pointless_overrides: &[Override],
+) -> Result<Vec<FileFix>> {
+    let mut fixes = Vec::new();
+    for source in sources {
+        // Only files on disk can be rewritten
+        if source.label == STDIN_LABEL || is_url(Path::new(&source.label)) {
+            continue;
+        }
+        // Deleting lines from JSON would leave its commas unbalanced
+        if InputFormat::detect(&source.label) == Some(InputFormat::Json) {
+            continue;
+        }
+        let targets: Vec<&Override> = pointless_overrides
+            .iter()
+            .filter(|item| item.file == source.label && item.previous_file != item.file)
+            .collect();
+        if targets.is_empty() {
+            continue;
+        }
+
+        let removed_lines = plan_file(source, &targets)?;
+        if !removed_lines.is_empty() {
+            fixes.push(FileFix {
+                file: source.label.clone(),
+                removed_lines,
+            });
+        }
+    }
+    Ok(fixes)
+}
+
+fn plan_file(source: &SourceFile, targets: &[&Override]) -> Result<BTreeSet<usize>> {
+    // Every assignment in a dotenv file is a line of its own
+    if InputFormat::detect(&source.label) == Some(InputFormat::Dotenv) {
+        return Ok(targets.iter().map(|target| target.line).collect());
+    }
+
+    let collector = parse_source(source, 0)?;
+    let lines: Vec<&str> = source.content.lines().collect();
+    let entries: Vec<(&Vec<String>, &ValueWithLocation, (usize, usize))> = collector
+        .values
+        .iter()
+        .map(|(path, value)| (path, value, line_range(value, &lines)))
+        .collect();
+
+    let opens_item = |line: usize| {
+        lines
+            .get(line - 1)
+            .is_some_and(|text| text.trim_start().starts_with('-'))
+    };
+
+    let mut removed: Vec<bool> = entries
+        .iter()
+        .map(|(path, value, _)| {
+            !value.from_alias
+                && !value.parents.contains(&ValueType::Sequence)
+                && !opens_item(value.key_line)
+                && targets.iter().any(|target| {
+                    target.path == **path
+                        && target.line == value.line
+                        && target.column == value.column
+                })
+        })
+        .collect();
+
+    // Keep anything that shares a line with a value we're keeping
+    let overlaps = |a: (usize, usize), b: (usize, usize)| a.0 <= b.1 && b.0 <= a.1;
+    loop {
+        let mut changed = false;
+        for index in 0..entries.len() {
+            if removed[index]
+                && entries
+                    .iter()
+                    .enumerate()
+                    .any(|(other, entry)| !removed[other] && overlaps(entry.2, entries[index].2))
+            {
+                removed[index] = false;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut removed_lines = BTreeSet::new();
+    for (index, (_, _, (start, end))) in entries.iter().enumerate() {
+        if removed[index] {
+            removed_lines.extend(*start..=*end);
+        }
+    }
+
+    // Drop parent keys left with nothing under them
+    let mut parents: BTreeSet<(usize, Vec<String>)> = BTreeSet::new();
+    for (index, (path, value, _)) in entries.iter().enumerate() {
+        if removed[index] {
+            for len in 1..path.len() {
+                parents.insert((value.document, path[..len].to_vec()));
+            }
+        }
+    }
+    // Deepest first, so a parent sees whether its nested mappings went
+    let mut parents: Vec<_> = parents.into_iter().collect();
+    parents.sort_by_key(|(_, prefix)| std::cmp::Reverse(prefix.len()));
+    for (document, prefix) in parents {
+        let emptied = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, (path, value, _))| value.document == document && path.starts_with(&prefix))
+            .all(|(index, _)| removed[index])
+            && collector
+                .collection_key_lines
+                .iter()
+                .filter(|((key_document, path), _)| {
+                    *key_document == document
+                        && path.len() > prefix.len()
+                        && path.starts_with(&prefix)
+                })
+                .all(|(_, line)| removed_lines.contains(line));
+        let Some(&key_line) = collector.collection_key_lines.get(&(document, prefix)) else {
+            continue;
+        };
+        let shared = entries
+            .iter()
+            .enumerate()
+            .any(|(index, entry)| !removed[index] && overlaps(entry.2, (key_line, key_line)));
+        if emptied && !shared && !opens_item(key_line) {
+            removed_lines.insert(key_line);
+        }
+    }
+    Ok(removed_lines)
+}
+
+/// First and last line a value occupies, key included. A span that ends at
+/// the start of a line (as block scalars and sequences do) stops on the line
+/// before.
+fn line_range(value: &ValueWithLocation, lines: &[&str]) -> (usize, usize) {
+    let mut end = value.end_line;
+    if end > value.line {
+        let before_end = lines
+            .get(end - 1)
+            .map(|text| text.chars().take(value.end_column - 1).collect::<String>())
+            .unwrap_or_default();
+        if before_end.trim().is_empty() {
+            end -= 1;
+        }
+    }
+    (value.key_line.min(value.line), end.max(value.line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PointlessPointer;
+
+    fn fixes(base: &str, overlay: &str) -> Vec<FileFix> {
+        let sources = vec![
+            SourceFile {
+                label: "values.yaml".to_string(),
+                content: base.to_string(),
+            },
+            SourceFile {
+                label: "prod.yaml".to_string(),
+                content: overlay.to_string(),
+            },
+        ];
+        let (pointless, _) = PointlessPointer::from_sources(Vec::new())
+            .analyze_sources(&sources)
+            .unwrap();
+        plan_fixes(&sources, &pointless).unwrap()
+    }
+
+    #[test]
+    fn removes_pointless_mapping_keys() {
+        let fixes = fixes(
+            "image:\n  repository: nginx\n  tag: v1\n",
+            "image:\n  tag: v1\nreplicas: 3\n",
+        );
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].removed_lines, BTreeSet::from([1, 2]));
+    }
+
+    #[test]
+    fn leaves_sequence_items_whole() {
+        let fixes = fixes(
+            "containers:\n  - name: app\n    image: nginx\n  - name: side\n    image: busybox\n",
+            "containers:\n  - name: app\n    image: nginx\n",
+        );
+        assert!(fixes.is_empty());
+    }
+}
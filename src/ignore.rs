@@ -0,0 +1,49 @@
+//! Suppressing findings whose path matches user-supplied glob patterns.
+
+use crate::{AnalyzeError, parse_canonical_path};
+use anyhow::Result;
+use glob::Pattern;
+
+/// Dotted-path globs such as `image.*` or `*.replicaCount`, matched against
+/// the joined path of each finding, and exact paths in canonical form such
+/// as `$.annotations["example.com/team"]`, for keys a glob can't single out.
+#[derive(Debug, Clone, Default)]
+pub struct IgnorePatterns {
+    patterns: Vec<Pattern>,
+    exact: Vec<Vec<String>>,
+}
+
+impl IgnorePatterns {
+    /// Patterns starting with `$` are canonical paths, the rest globs.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        Ok(Self::parse(patterns)?)
+    }
+
+    pub(crate) fn parse(patterns: &[String]) -> Result<Self, AnalyzeError> {
+        let mut ignore = Self::default();
+        for pattern in patterns {
+            let invalid = |reason| AnalyzeError::Ignore {
+                pattern: pattern.clone(),
+                reason,
+            };
+            if pattern.starts_with('$') {
+                ignore
+                    .exact
+                    .push(parse_canonical_path(pattern).map_err(invalid)?);
+            } else {
+                ignore
+                    .patterns
+                    .push(Pattern::new(pattern).map_err(|error| invalid(error.msg.to_string()))?);
+            }
+        }
+        Ok(ignore)
+    }
+
+    pub fn is_ignored(&self, path: &[String]) -> bool {
+        if self.exact.iter().any(|exact| exact == path) {
+            return true;
+        }
+        let joined = path.join(".");
+        self.patterns.iter().any(|pattern| pattern.matches(&joined))
+    }
+}
@@ -1,337 +1,4666 @@
-use anyhow::Result;
+use cache::Cache;
 use colored::Colorize;
-use saphyr_parser::{Event, Parser, Span, SpannedEventReceiver};
-use std::collections::HashMap;
+use glob::Pattern;
+use ignore::IgnorePatterns;
+use normalizer::{Chain, Equivalences, ExpandEnv, LowercaseKeys, Quantities, YamlScalars};
+use rayon::ThreadPoolBuilder;
+use rayon::prelude::*;
+use saphyr_parser::{Event, Marker, Parser, ScalarStyle, Span, SpannedEventReceiver, Tag};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::fs;
-use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone)]
+pub mod baseline;
+mod builder;
+mod cache;
+mod dotenv;
+mod env;
+mod error;
+pub mod fix;
+pub mod ignore;
+mod merge;
+mod normalize;
+pub mod normalizer;
+mod quantity;
+pub mod redact;
+mod remote;
+pub mod report;
+mod set;
+#[cfg(test)]
+mod tests;
+
+pub use builder::PointlessPointerBuilder;
+pub use error::AnalyzeError;
+pub use normalizer::{KeyNormalizer, ValueNormalizer};
+
+type Result<T, E = AnalyzeError> = std::result::Result<T, E>;
+
+/// Render a path for display: segments joined by `.`, with any segment that
+/// would be ambiguous (containing a dot, bracket or quote, or empty) written
+/// JSONPath-style as `["a.b"]`. The empty path of a document that is a single
+/// value is `(root)`.
+pub fn format_path(path: &[String]) -> String {
+    if path.is_empty() {
+        return "(root)".to_string();
+    }
+    let mut formatted = String::new();
+    for segment in path {
+        let plain = !segment.is_empty() && !segment.contains(['.', '[', ']', '"', '\\']);
+        if plain {
+            if !formatted.is_empty() {
+                formatted.push('.');
+            }
+            formatted.push_str(segment);
+        } else {
+            let escaped = segment.replace('\\', "\\\\").replace('"', "\\\"");
+            formatted.push_str(&format!("[\"{escaped}\"]"));
+        }
+    }
+    formatted
+}
+
+/// Render a path as an unambiguous JSONPath-like string for tools, which
+/// [`parse_canonical_path`] reads back: `$` followed by `.name` for a segment
+/// made of ASCII letters, digits and `_` that doesn't start with a digit,
+/// `[N]` for a number (a list index, or a mapping key that is one), and
+/// `["..."]`, escaped as a JSON string, for anything else. The empty path is
+/// `$`.
+pub fn canonical_path(path: &[String]) -> String {
+    let mut canonical = String::from("$");
+    for segment in path {
+        let name = segment
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if name {
+            canonical.push('.');
+            canonical.push_str(segment);
+        } else if is_index(segment) {
+            canonical.push_str(&format!("[{segment}]"));
+        } else {
+            canonical.push('[');
+            canonical.push_str(&serde_json::to_string(segment).unwrap_or_default());
+            canonical.push(']');
+        }
+    }
+    canonical
+}
+
+/// Whether a segment is written as a number, without leading zeros.
+fn is_index(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment.chars().all(|c| c.is_ascii_digit())
+        && (segment == "0" || !segment.starts_with('0'))
+}
+
+/// Read a path written by [`canonical_path`] back into its segments.
+///
+/// ```
+/// use pointless_pointer::{canonical_path, parse_canonical_path};
+///
+/// let path: Vec<String> = ["a", "b", "0", "dotted.key", "", "say \"hi\""]
+///     .map(String::from)
+///     .to_vec();
+/// let canonical = canonical_path(&path);
+/// assert_eq!(canonical, r#"$.a.b[0]["dotted.key"][""]["say \"hi\""]"#);
+/// assert_eq!(parse_canonical_path(&canonical).unwrap(), path);
+/// ```
+pub fn parse_canonical_path(text: &str) -> Result<Vec<String>, String> {
+    let Some(mut rest) = text.strip_prefix('$') else {
+        return Err(format!("`{text}` doesn't start with `$`"));
+    };
+    let mut path = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            let name = &after[..end];
+            if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+                return Err(format!("`{text}` has `.` without a name after it"));
+            }
+            path.push(name.to_string());
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix("[\"") {
+            let mut escaped = false;
+            let close = after.char_indices().find_map(|(index, c)| match c {
+                _ if escaped => {
+                    escaped = false;
+                    None
+                }
+                '\\' => {
+                    escaped = true;
+                    None
+                }
+                '"' => Some(index),
+                _ => None,
+            });
+            let Some(close) = close else {
+                return Err(format!("`{text}` has an unterminated string"));
+            };
+            let segment: String = serde_json::from_str(&rest[1..close + 3])
+                .map_err(|error| format!("`{text}` has an invalid string: {error}"))?;
+            path.push(segment);
+            rest = after[close + 1..]
+                .strip_prefix(']')
+                .ok_or_else(|| format!("`{text}` has a string without `]` after it"))?;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let Some(end) = after.find(']') else {
+                return Err(format!("`{text}` has `[` without `]`"));
+            };
+            let index = &after[..end];
+            if !is_index(index) {
+                return Err(format!("`[{index}]` in `{text}` is not a list index"));
+            }
+            path.push(index.to_string());
+            rest = &after[end + 1..];
+        } else {
+            return Err(format!("`{text}` has `{rest}` where `.` or `[` should be"));
+        }
+    }
+    Ok(path)
+}
+
+/// How much a finding matters, least first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Harmless noise, such as a value repeated within a file
+    Info,
+    /// Worth cleaning up, such as an override that changes nothing
+    Warning,
+    /// Makes the configuration ambiguous, such as a key given two values
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Override {
+    pub severity: Severity,
     pub file: String,
     pub path: Vec<String>,
     pub value: String,
     pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
     pub previous_value: String,
     pub previous_file: String,
     pub previous_line: usize,
+    /// Every earlier layer that set this path, lowest first; only filled in
+    /// with [`PointlessPointer::show_chain`]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub chain: Vec<ChainLink>,
+}
+
+/// One earlier layer's setting of an overridden path.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainLink {
+    pub file: String,
+    pub line: usize,
+    pub value: String,
+    /// Whether this is the same value the override sets
+    pub same_value: bool,
+}
+
+/// `  Chain: a.yaml:1 (x) -> b.yaml:2 (y)`, if there is a chain to show.
+fn write_chain(f: &mut fmt::Formatter<'_>, chain: &[ChainLink]) -> fmt::Result {
+    if chain.is_empty() {
+        return Ok(());
+    }
+    let links: Vec<String> = chain
+        .iter()
+        .map(|link| format!("{}:{} ({})", link.file, link.line, abbreviate(&link.value)))
+        .collect();
+    writeln!(f, "  {} {}", "Chain:".bold(), links.join(" -> "))
+}
+
+/// Lists longer than this are shown by their first few items and a count.
+const MAX_SHOWN_ITEMS: usize = 10;
+
+/// A list of scalars, as rendered in flow style, cut down to
+/// `[a, b, c, … (N items)]` when it is long; anything else as it is.
+fn abbreviate(value: &str) -> Cow<'_, str> {
+    let Some(inner) = value
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+    else {
+        return Cow::Borrowed(value);
+    };
+    // Item boundaries: `, ` outside nested lists and quotes
+    let mut starts = vec![0];
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut chars = inner.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match (quote, c) {
+            (Some('"'), _) if escaped => escaped = false,
+            (Some('"'), '\\') => escaped = true,
+            (Some(open), _) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 && chars.peek().is_some_and(|&(_, next)| next == ' ') => {
+                starts.push(index + 2);
+            }
+            _ => {}
+        }
+    }
+    if starts.len() <= MAX_SHOWN_ITEMS {
+        return Cow::Borrowed(value);
+    }
+    Cow::Owned(format!(
+        "[{}, … ({} items)]",
+        &inner[..starts[3] - 2],
+        starts.len()
+    ))
+}
+
+/// ` (lines 3-7)` for a value spanning several lines, such as a block list.
+/// A span that ends at the start of a line (as block scalars do) stops on the
+/// line before.
+fn line_range(line: usize, end_line: usize, end_column: usize) -> String {
+    let end_line = if end_column == 1 {
+        end_line.saturating_sub(1)
+    } else {
+        end_line
+    };
+    if end_line > line {
+        format!(" (lines {line}-{end_line})")
+    } else {
+        String::new()
+    }
+}
+
+impl Override {
+    /// The path in the form tools can rely on, see [`canonical_path`]; the
+    /// `Display` output uses the friendlier [`format_path`].
+    pub fn canonical_path(&self) -> String {
+        canonical_path(&self.path)
+    }
+
+    /// Why this is reported, in a sentence naming the locations involved.
+    pub fn explanation(&self) -> String {
+        if self.file == self.previous_file {
+            format!(
+                "The key is already set to the same value at {}:{} in this file, so repeating it has no effect.",
+                self.previous_file, self.previous_line
+            )
+        } else {
+            format!(
+                "This override sets the same value already effective from {}:{}, so it has no effect.",
+                self.previous_file, self.previous_line
+            )
+        }
+    }
 }
 
 impl fmt::Display for Override {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "  {} {}:{}", "File:".bold(), self.file, self.line)?;
-        writeln!(f, "  {} {}", "Path:".bold(), self.path.join("."))?;
-        writeln!(f, "  {} {}", "Value:".bold(), self.value)?;
+        writeln!(
+            f,
+            "  {} {}:{}:{}{}",
+            "File:".bold(),
+            self.file,
+            self.line,
+            self.column,
+            line_range(self.line, self.end_line, self.end_column)
+        )?;
+        writeln!(f, "  {} {}", "Path:".bold(), format_path(&self.path))?;
+        writeln!(f, "  {} {}", "Value:".bold(), abbreviate(&self.value))?;
         writeln!(
             f,
             "  {} {} (from {}:{})",
             "Same as:".bold(),
-            self.previous_value,
+            abbreviate(&self.previous_value),
             self.previous_file,
             self.previous_line
         )?;
+        write_chain(f, &self.chain)?;
+        writeln!(f, "  {} {}", "Severity:".bold(), self.severity)?;
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DuplicateKeyWarning {
+    pub severity: Severity,
     pub file: String,
     pub path: Vec<String>,
     pub first_value: String,
     pub first_line: usize,
     pub second_value: String,
     pub second_line: usize,
+    /// Span of the second occurrence's value
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    /// Where each occurrence's key was typed, which the warning points at
+    pub first_key_line: usize,
+    pub first_key_column: usize,
+    pub second_key_line: usize,
+    pub second_key_column: usize,
+    /// The values are the same once leading and trailing whitespace is
+    /// trimmed, so the conflict is likely a typo rather than intended
+    pub whitespace_only: bool,
+}
+
+impl DuplicateKeyWarning {
+    /// See [`Override::canonical_path`].
+    pub fn canonical_path(&self) -> String {
+        canonical_path(&self.path)
+    }
+
+    /// Why this is reported, in a sentence naming the locations involved.
+    pub fn explanation(&self) -> String {
+        format!(
+            "When a YAML mapping repeats a key, the later one silently wins, so the value at line {} replaces the one at line {} of {}.",
+            self.second_key_line, self.first_key_line, self.file
+        )
+    }
 }
 
 impl fmt::Display for DuplicateKeyWarning {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "  {} {}", "File:".bold(), self.file)?;
-        writeln!(f, "  {} {}", "Path:".bold(), self.path.join("."))?;
         writeln!(
             f,
-            "  {} {} (line {})",
+            "  {} {}:{}:{}",
+            "File:".bold(),
+            self.file,
+            self.second_key_line,
+            self.second_key_column
+        )?;
+        writeln!(f, "  {} {}", "Path:".bold(), format_path(&self.path))?;
+        writeln!(
+            f,
+            "  {} {} (key at {}:{})",
             "First value:".bold(),
-            self.first_value,
-            self.first_line
+            abbreviate(&self.first_value),
+            self.first_key_line,
+            self.first_key_column
         )?;
         writeln!(
             f,
-            "  {} {} (line {})",
+            "  {} {} (key at {}:{})",
             "Second value:".bold(),
-            self.second_value,
-            self.second_line
+            abbreviate(&self.second_value),
+            self.second_key_line,
+            self.second_key_column
         )?;
+        if self.whitespace_only {
+            writeln!(
+                f,
+                "  {} {:?} and {:?} only differ in surrounding whitespace, likely by accident",
+                "Note:".bold(),
+                self.first_value,
+                self.second_value
+            )?;
+        }
+        writeln!(f, "  {} {}", "Severity:".bold(), self.severity)?;
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
-struct ValueWithLocation {
-    value: String,
-    file: String,
-    line: usize,
-}
-
-#[derive(Debug)]
-enum ParseState {
-    Idle,
-    ExpectingKey,
-    ExpectingValue(String), // The key
-    InSequence,
+/// An override that actually changes the value in effect below it.
+#[derive(Debug, Clone, Serialize)]
+pub struct EffectiveOverride {
+    pub file: String,
+    pub path: Vec<String>,
+    pub value: String,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub previous_value: String,
+    pub previous_file: String,
+    pub previous_line: usize,
+    /// The list holds the same items as before, only in a different order
+    pub reorder_only: bool,
+    /// See [`Override::chain`]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub chain: Vec<ChainLink>,
 }
 
-struct YamlValueCollector {
-    values: Vec<(Vec<String>, ValueWithLocation)>, // Using Vec to preserve order and handle duplicates
-    current_path: Vec<String>,
-    current_file: String,
-    state: ParseState,
-    sequence_index: usize,
-    mapping_depth: usize,
-    current_sequence_items: Vec<String>, // Collect items in current sequence
-    sequence_start_line: usize,
-    sequence_depth: usize, // Track how deeply nested we are in sequences
+impl EffectiveOverride {
+    /// See [`Override::canonical_path`].
+    pub fn canonical_path(&self) -> String {
+        canonical_path(&self.path)
+    }
 }
 
-impl YamlValueCollector {
-    fn new(file: String) -> Self {
-        Self {
-            values: Vec::new(),
-            current_path: Vec::new(),
-            current_file: file,
-            state: ParseState::Idle,
-            sequence_index: 0,
-            mapping_depth: 0,
-            current_sequence_items: Vec::new(),
-            sequence_start_line: 0,
-            sequence_depth: 0,
+impl fmt::Display for EffectiveOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} {}:{}:{}{}",
+            "File:".bold(),
+            self.file,
+            self.line,
+            self.column,
+            line_range(self.line, self.end_line, self.end_column)
+        )?;
+        writeln!(f, "  {} {}", "Path:".bold(), format_path(&self.path))?;
+        writeln!(f, "  {} {}", "Value:".bold(), abbreviate(&self.value))?;
+        writeln!(
+            f,
+            "  {} {} (from {}:{})",
+            "Changed from:".bold(),
+            abbreviate(&self.previous_value),
+            self.previous_file,
+            self.previous_line
+        )?;
+        if self.reorder_only {
+            writeln!(f, "  {} only the order of the list changed", "Note:".bold())?;
+        }
+        write_chain(f, &self.chain)?;
+        // A value set further down that a later layer changed, restored here
+        if let Some(restored) = self.chain.iter().rposition(|link| link.same_value)
+            && let Some(changed) = self.chain.get(restored + 1)
+        {
+            writeln!(
+                f,
+                "  {} restores the value from {}:{} that {}:{} changed",
+                "Note:".bold(),
+                self.chain[restored].file,
+                self.chain[restored].line,
+                changed.file,
+                changed.line
+            )?;
         }
+        Ok(())
     }
 }
 
-impl<'input> SpannedEventReceiver<'input> for YamlValueCollector {
-    fn on_event(&mut self, event: Event<'input>, span: Span) {
-        match event {
-            Event::MappingStart(_, _) => {
-                if let ParseState::ExpectingValue(key) = &self.state {
-                    // This is a nested mapping as a value
-                    self.current_path.push(key.clone());
-                }
-                self.mapping_depth += 1;
-                // If we're in a sequence, stay in the InSequence state
-                if self.sequence_depth == 0 {
-                    self.state = ParseState::ExpectingKey;
-                }
-            }
-            Event::MappingEnd => {
-                self.mapping_depth -= 1;
-                if !self.current_path.is_empty()
-                    && self.current_path.len() >= self.mapping_depth
-                    && self.sequence_depth == 0
-                {
-                    self.current_path.pop();
-                }
-                // If we're not in a sequence, update the state
-                if self.sequence_depth == 0 {
-                    self.state = if self.mapping_depth > 0 {
-                        ParseState::ExpectingKey
-                    } else {
-                        ParseState::Idle
-                    };
-                }
-            }
-            Event::SequenceStart(_, _) => {
-                self.sequence_depth += 1;
-                if let ParseState::ExpectingValue(key) = &self.state {
-                    // This is a sequence as a value - start collecting sequence items
-                    self.current_path.push(key.clone());
-                    self.current_sequence_items.clear();
-                    self.sequence_start_line = span.start.line();
-                }
-                self.state = ParseState::InSequence;
-                self.sequence_index = 0;
-            }
-            Event::SequenceEnd => {
-                self.sequence_depth -= 1;
-                // End of sequence - record the entire sequence as one value
-                if !self.current_path.is_empty() && self.sequence_depth == 0 {
-                    let sequence_value = format!("[{}]", self.current_sequence_items.join(", "));
-                    self.values.push((
-                        self.current_path.clone(),
-                        ValueWithLocation {
-                            value: sequence_value,
-                            file: self.current_file.clone(),
-                            line: self.sequence_start_line,
-                        },
-                    ));
-                    self.current_path.pop();
-                }
-                self.current_sequence_items.clear();
-                self.state = if self.mapping_depth > 0 {
-                    ParseState::ExpectingKey
-                } else {
-                    ParseState::Idle
-                };
-            }
-            Event::Scalar(value, _, _, _) => {
-                match &self.state {
-                    ParseState::ExpectingKey => {
-                        // This is a key
-                        self.state = ParseState::ExpectingValue(value.into_owned());
-                    }
-                    ParseState::ExpectingValue(key) => {
-                        // This is a scalar value for the key
-                        // Only collect values if we're not inside a sequence
-                        if self.sequence_depth == 0 {
-                            let mut value_path = self.current_path.clone();
-                            value_path.push(key.clone());
-
-                            let line = span.start.line();
-                            self.values.push((
-                                value_path,
-                                ValueWithLocation {
-                                    value: value.into_owned(),
-                                    file: self.current_file.clone(),
-                                    line,
-                                },
-                            ));
-                        }
+/// A value nested more deeply than the configured maximum.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeepPathWarning {
+    pub severity: Severity,
+    pub file: String,
+    pub path: Vec<String>,
+    pub line: usize,
+    pub column: usize,
+    pub max_depth: usize,
+}
 
-                        self.state = ParseState::ExpectingKey;
-                    }
-                    ParseState::InSequence => {
-                        // This is an item in a sequence - collect it
-                        self.current_sequence_items.push(format!("\"{value}\""));
-                        self.sequence_index += 1;
-                    }
-                    ParseState::Idle => {
-                        // Root level scalar
-                        let line = span.start.line();
-                        self.values.push((
-                            vec![],
-                            ValueWithLocation {
-                                value: value.into_owned(),
-                                file: self.current_file.clone(),
-                                line,
-                            },
-                        ));
-                    }
-                }
-            }
-            _ => {}
-        }
+impl DeepPathWarning {
+    /// See [`Override::canonical_path`].
+    pub fn canonical_path(&self) -> String {
+        canonical_path(&self.path)
+    }
+
+    /// Why this is reported, in a sentence naming the locations involved.
+    pub fn explanation(&self) -> String {
+        format!(
+            "This value is at depth {}, beyond the limit of {} set with --max-depth.",
+            self.path.len(),
+            self.max_depth
+        )
     }
 }
 
-pub struct PointlessPointer {
-    base_file: PathBuf,
-    override_files: Vec<PathBuf>,
+impl fmt::Display for DeepPathWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} {}:{}:{}",
+            "File:".bold(),
+            self.file,
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "  {} {}", "Path:".bold(), format_path(&self.path))?;
+        writeln!(
+            f,
+            "  {} {} (limit {})",
+            "Depth:".bold(),
+            self.path.len(),
+            self.max_depth
+        )?;
+        writeln!(f, "  {} {}", "Severity:".bold(), self.severity)?;
+        Ok(())
+    }
 }
 
-impl PointlessPointer {
-    pub fn new(base_file: PathBuf, override_files: Vec<PathBuf>) -> Self {
-        Self {
-            base_file,
-            override_files,
-        }
+/// What kind of value a path holds, as YAML resolves it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueType {
+    Null,
+    Bool,
+    Int,
+    Float,
+    String,
+    Sequence,
+    Mapping,
+}
+
+impl ValueType {
+    /// Whether replacing one with the other is an ordinary override: nulls
+    /// stand for "unset", and ints and floats are both numbers.
+    fn compatible(self, other: Self) -> bool {
+        use ValueType::{Float, Int, Null};
+        self == other
+            || self == Null
+            || other == Null
+            || matches!((self, other), (Int, Float) | (Float, Int))
     }
+}
 
-    pub fn analyze(&self) -> Result<(Vec<Override>, Vec<DuplicateKeyWarning>)> {
-        // Collect all values from all files
-        let mut all_values: Vec<Vec<(Vec<String>, ValueWithLocation)>> = Vec::new();
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ValueType::Null => "null",
+            ValueType::Bool => "bool",
+            ValueType::Int => "int",
+            ValueType::Float => "float",
+            ValueType::String => "string",
+            ValueType::Sequence => "sequence",
+            ValueType::Mapping => "mapping",
+        };
+        f.write_str(name)
+    }
+}
 
-        // Process base file
-        let base_content = fs::read_to_string(&self.base_file)?;
-        let mut base_collector = YamlValueCollector::new(self.base_file.display().to_string());
-        let mut parser = Parser::new_from_str(&base_content);
-        parser.load(&mut base_collector, true)?;
-        all_values.push(base_collector.values);
+/// A later layer giving a path a different type than the layers below it,
+/// e.g. a string where there was an int, or a mapping where there was a list.
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeMismatchWarning {
+    pub severity: Severity,
+    pub file: String,
+    pub path: Vec<String>,
+    pub line: usize,
+    pub column: usize,
+    pub value_type: ValueType,
+    /// The value as written; `None` when the path now holds a collection
+    pub value: Option<String>,
+    pub previous_file: String,
+    pub previous_line: usize,
+    pub previous_type: ValueType,
+    /// See [`value`](Self::value)
+    pub previous_value: Option<String>,
+}
 
-        // Process override files
-        for override_file in &self.override_files {
-            let content = fs::read_to_string(override_file)?;
-            let mut collector = YamlValueCollector::new(override_file.display().to_string());
-            let mut parser = Parser::new_from_str(&content);
-            parser.load(&mut collector, true)?;
-            all_values.push(collector.values);
-        }
+impl TypeMismatchWarning {
+    /// See [`Override::canonical_path`].
+    pub fn canonical_path(&self) -> String {
+        canonical_path(&self.path)
+    }
 
-        Ok(find_pointless_overrides_and_warnings(&all_values))
+    /// Why this is reported, in a sentence naming the locations involved.
+    pub fn explanation(&self) -> String {
+        format!(
+            "This value has type {} where {}:{} has type {}, which templates written for the earlier type may not handle.",
+            self.value_type, self.previous_file, self.previous_line, self.previous_type
+        )
     }
 }
 
-fn find_pointless_overrides_and_warnings(
-    all_values: &[Vec<(Vec<String>, ValueWithLocation)>],
-) -> (Vec<Override>, Vec<DuplicateKeyWarning>) {
-    let mut pointless = Vec::new();
-    let mut warnings = Vec::new();
+impl fmt::Display for TypeMismatchWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |value_type: ValueType, value: &Option<String>| match value {
+            Some(value) => format!("{value_type} {}", abbreviate(value)),
+            None => value_type.to_string(),
+        };
+        writeln!(
+            f,
+            "  {} {}:{}:{}",
+            "File:".bold(),
+            self.file,
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "  {} {}", "Path:".bold(), format_path(&self.path))?;
+        writeln!(
+            f,
+            "  {} {}",
+            "Type:".bold(),
+            describe(self.value_type, &self.value)
+        )?;
+        writeln!(
+            f,
+            "  {} {} (from {}:{})",
+            "Was:".bold(),
+            describe(self.previous_type, &self.previous_value),
+            self.previous_file,
+            self.previous_line
+        )?;
+        writeln!(f, "  {} {}", "Severity:".bold(), self.severity)?;
+        Ok(())
+    }
+}
 
-    // Check for duplicates within each file first
-    for values in all_values.iter() {
-        let mut seen_in_file: HashMap<Vec<String>, &ValueWithLocation> = HashMap::new();
+/// A later layer replacing a null with an empty string, or an empty string
+/// with a null. Helm treats the two differently (a null removes the key),
+/// but `foo:` and `foo: ""` are easily taken for each other.
+#[derive(Debug, Clone, Serialize)]
+pub struct NullEmptyWarning {
+    pub severity: Severity,
+    pub file: String,
+    pub path: Vec<String>,
+    pub line: usize,
+    pub column: usize,
+    /// Whether this layer's value is the null, and the earlier one the empty
+    /// string
+    pub is_null: bool,
+    /// The value as recorded, such as `""` or `~`
+    pub value: String,
+    pub previous_file: String,
+    pub previous_line: usize,
+    /// See [`value`](Self::value)
+    pub previous_value: String,
+}
 
-        for (path, value_loc) in values {
-            if let Some(previous_in_file) = seen_in_file.get(path) {
-                // Found a duplicate within the same file
-                if value_loc.value == previous_in_file.value {
-                    pointless.push(Override {
-                        file: value_loc.file.clone(),
-                        path: path.clone(),
-                        value: value_loc.value.clone(),
-                        line: value_loc.line,
-                        previous_value: previous_in_file.value.clone(),
-                        previous_file: previous_in_file.file.clone(),
-                        previous_line: previous_in_file.line,
-                    });
-                } else {
-                    // Same key but different values - create a warning
-                    warnings.push(DuplicateKeyWarning {
-                        file: value_loc.file.clone(),
-                        path: path.clone(),
-                        first_value: previous_in_file.value.clone(),
-                        first_line: previous_in_file.line,
-                        second_value: value_loc.value.clone(),
-                        second_line: value_loc.line,
-                    });
-                }
-            }
-            seen_in_file.insert(path.clone(), value_loc);
-        }
+impl NullEmptyWarning {
+    /// See [`Override::canonical_path`].
+    pub fn canonical_path(&self) -> String {
+        canonical_path(&self.path)
     }
 
-    // Then check for overrides across files
-    if all_values.len() >= 2 {
-        // For each override file (starting from the second)
-        for i in 1..all_values.len() {
-            let current_values = &all_values[i];
+    /// Why this is reported, in a sentence naming the locations involved.
+    pub fn explanation(&self) -> String {
+        let (now, before) = if self.is_null {
+            ("a null", "an empty string")
+        } else {
+            ("an empty string", "a null")
+        };
+        format!(
+            "This value is {now} where {}:{} has {before}; Helm treats them differently, since a null removes the key.",
+            self.previous_file, self.previous_line
+        )
+    }
+}
 
-            // Build effective values up to the previous file
-            // Using HashMap to get the last value for each path (in case of duplicates)
-            let mut effective_values: HashMap<Vec<String>, ValueWithLocation> = HashMap::new();
-            for value in all_values.iter().take(i) {
-                for (path, value_loc) in value {
-                    effective_values.insert(path.clone(), value_loc.clone());
-                }
+impl fmt::Display for NullEmptyWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |is_null: bool| {
+            if is_null {
+                "null"
+            } else {
+                "\"\" (empty string)"
             }
+        };
+        writeln!(
+            f,
+            "  {} {}:{}:{}",
+            "File:".bold(),
+            self.file,
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "  {} {}", "Path:".bold(), format_path(&self.path))?;
+        writeln!(f, "  {} {}", "Value:".bold(), describe(self.is_null))?;
+        writeln!(
+            f,
+            "  {} {} (from {}:{})",
+            "Was:".bold(),
+            describe(!self.is_null),
+            self.previous_file,
+            self.previous_line
+        )?;
+        writeln!(f, "  {} {}", "Severity:".bold(), self.severity)?;
+        Ok(())
+    }
+}
 
-            // Check current file for pointless overrides
-            for (path, current_value) in current_values {
-                if let Some(previous_value) = effective_values.get(path) {
-                    if current_value.value == previous_value.value {
-                        pointless.push(Override {
-                            file: current_value.file.clone(),
-                            path: path.clone(),
-                            value: current_value.value.clone(),
-                            line: current_value.line,
-                            previous_value: previous_value.value.clone(),
-                            previous_file: previous_value.file.clone(),
-                            previous_line: previous_value.line,
-                        });
-                    }
-                }
-            }
-        }
+/// An override file setting a path to something else than an earlier
+/// override file does. Which of the two wins then depends only on the order
+/// the files are given in, which for files picked up by a glob may be
+/// accidental.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictWarning {
+    pub severity: Severity,
+    pub file: String,
+    pub path: Vec<String>,
+    pub line: usize,
+    pub column: usize,
+    pub value: String,
+    pub previous_file: String,
+    pub previous_line: usize,
+    pub previous_value: String,
+}
+
+impl ConflictWarning {
+    /// See [`Override::canonical_path`].
+    pub fn canonical_path(&self) -> String {
+        canonical_path(&self.path)
     }
 
-    (pointless, warnings)
+    /// Why this is reported, in a sentence naming the locations involved.
+    pub fn explanation(&self) -> String {
+        format!(
+            "{}:{} sets this path to `{}` and this file to `{}`; which one takes effect depends only on the order the files are given in.",
+            self.previous_file,
+            self.previous_line,
+            abbreviate(&self.previous_value),
+            abbreviate(&self.value)
+        )
+    }
+}
+
+impl fmt::Display for ConflictWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} {}:{}:{}",
+            "File:".bold(),
+            self.file,
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "  {} {}", "Path:".bold(), format_path(&self.path))?;
+        writeln!(f, "  {} {}", "Value:".bold(), abbreviate(&self.value))?;
+        writeln!(
+            f,
+            "  {} {} (from {}:{})",
+            "Conflicts with:".bold(),
+            abbreviate(&self.previous_value),
+            self.previous_file,
+            self.previous_line
+        )?;
+        writeln!(f, "  {} {}", "Severity:".bold(), self.severity)?;
+        Ok(())
+    }
+}
+
+/// A layer whose every value is a pointless override, so removing the file
+/// changes nothing.
+#[derive(Debug, Clone, Serialize)]
+pub struct RedundantFile {
+    pub file: String,
+    /// How many values the file sets, all of them already in effect
+    pub values: usize,
+}
+
+impl fmt::Display for RedundantFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} {} is entirely redundant ({} value(s), all already in effect)",
+            "Overlay:".bold(),
+            self.file,
+            self.values
+        )
+    }
+}
+
+/// A mapping or list in a layer whose every value is a pointless override,
+/// so the whole subtree can go rather than each value on its own.
+#[derive(Debug, Clone, Serialize)]
+pub struct RedundantSubtree {
+    pub severity: Severity,
+    pub file: String,
+    pub path: Vec<String>,
+    /// Where the subtree's first value is
+    pub line: usize,
+    pub column: usize,
+    /// How many values the subtree sets, all of them already in effect
+    pub values: usize,
+}
+
+impl fmt::Display for RedundantSubtree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} {}:{}:{}",
+            "File:".bold(),
+            self.file,
+            self.line,
+            self.column
+        )?;
+        writeln!(f, "  {} {}", "Path:".bold(), format_path(&self.path))?;
+        writeln!(
+            f,
+            "  {} entire subtree redundant ({} value(s), all already in effect)",
+            "Subtree:".bold(),
+            self.values
+        )?;
+        writeln!(f, "  {} {}", "Severity:".bold(), self.severity)?;
+        Ok(())
+    }
+}
+
+impl RedundantSubtree {
+    /// Why this is reported, in a sentence.
+    pub fn explanation(&self) -> String {
+        format!(
+            "Each of the {} values under this key restates a value already in effect from an earlier file, so the whole subtree can be removed.",
+            self.values
+        )
+    }
+}
+
+/// How much of a layer above the lowest is pointless, for deciding which
+/// files to clean up first.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileStats {
+    pub file: String,
+    /// Distinct paths the file sets
+    pub total_paths: usize,
+    /// Distinct paths among those with a pointless override
+    pub pointless_paths: usize,
+    /// `pointless_paths / total_paths`
+    pub redundancy_ratio: f64,
+}
+
+impl fmt::Display for FileStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.total_paths == 0 {
+            return writeln!(f, "  {} sets no values", format!("{}:", self.file).bold());
+        }
+        writeln!(
+            f,
+            "  {} {} of {} path(s) pointless ({:.1}%)",
+            format!("{}:", self.file).bold(),
+            self.pointless_paths,
+            self.total_paths,
+            self.redundancy_ratio * 100.0
+        )
+    }
+}
+
+/// Which layer's value is in effect for a path, and how often the path is
+/// set along the way.
+#[derive(Debug, Clone, Serialize)]
+pub struct PathProvenance {
+    pub path: Vec<String>,
+    /// The last layer to set the path, whose value wins
+    pub file: String,
+    pub line: usize,
+    /// How many times the path is set, counting every layer
+    pub writes: usize,
+    /// How many of those settings a later one replaces, `writes - 1`
+    pub overridden: usize,
+}
+
+impl fmt::Display for PathProvenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} {}:{} (set {} time(s), overridden {})",
+            format!("{}:", format_path(&self.path)).bold(),
+            self.file,
+            self.line,
+            self.writes,
+            self.overridden
+        )
+    }
+}
+
+/// A value of the lowest layer that no layer above it sets, replaces or sets
+/// anything under, so it may be configuration nothing needs.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadKey {
+    pub file: String,
+    pub path: Vec<String>,
+    pub value: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for DeadKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} {} ({}:{})",
+            format!("{}:", format_path(&self.path)).bold(),
+            abbreviate(&self.value),
+            self.file,
+            self.line
+        )
+    }
+}
+
+impl DeadKey {
+    /// See [`Override::canonical_path`].
+    pub fn canonical_path(&self) -> String {
+        canonical_path(&self.path)
+    }
+}
+
+/// A layer that sets no values at all, so it does nothing.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmptyFile {
+    pub file: String,
+    /// The file holds nothing but whitespace; otherwise it has content, such
+    /// as comments, empty documents or a lone scalar, that sets nothing
+    pub blank: bool,
+}
+
+impl fmt::Display for EmptyFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = if self.blank {
+            "is empty"
+        } else {
+            "has content but sets no values"
+        };
+        writeln!(f, "  {} {} {what}", "Overlay:".bold(), self.file)
+    }
+}
+
+/// A layer left out of the analysis because it couldn't be read.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedFile {
+    pub file: String,
+    pub error: String,
+}
+
+impl fmt::Display for SkippedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  {} {}", "File:".bold(), self.file)?;
+        writeln!(f, "  {} {}", "Error:".bold(), self.error)?;
+        Ok(())
+    }
+}
+
+/// A single result of the analysis.
+#[derive(Debug, Clone)]
+pub enum Finding {
+    PointlessOverride(Override),
+    DuplicateKey(DuplicateKeyWarning),
+    /// Only produced when [`PointlessPointer::show_effective`] is enabled
+    EffectiveOverride(EffectiveOverride),
+    /// Only produced when [`PointlessPointer::max_depth`] is set
+    DeepPath(DeepPathWarning),
+    TypeMismatch(TypeMismatchWarning),
+    /// Only produced when [`PointlessPointer::warn_null_empty`] is enabled
+    NullEmpty(NullEmptyWarning),
+    /// Only produced when [`PointlessPointer::warn_conflicts`] is enabled
+    Conflict(ConflictWarning),
+    RedundantFile(RedundantFile),
+    /// Only produced when [`PointlessPointer::rollup`] is enabled, alongside
+    /// the pointless overrides it covers
+    RedundantSubtree(RedundantSubtree),
+    EmptyFile(EmptyFile),
+    /// Only produced when [`PointlessPointer::stats`] is enabled
+    FileStats(FileStats),
+    /// Only produced when [`PointlessPointer::provenance`] is enabled
+    Provenance(PathProvenance),
+    /// Only produced when [`PointlessPointer::dead_keys`] is enabled
+    DeadKey(DeadKey),
+    /// Only produced when [`PointlessPointer::skip_missing`] is enabled
+    SkippedFile(SkippedFile),
+}
+
+impl Finding {
+    /// How much the finding matters. Findings that only describe the files,
+    /// rather than point out a problem, are [`Severity::Info`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            Finding::PointlessOverride(override_item) => override_item.severity,
+            Finding::DuplicateKey(warning) => warning.severity,
+            Finding::DeepPath(warning) => warning.severity,
+            Finding::TypeMismatch(warning) => warning.severity,
+            Finding::NullEmpty(warning) => warning.severity,
+            Finding::Conflict(warning) => warning.severity,
+            Finding::RedundantSubtree(subtree) => subtree.severity,
+            Finding::EffectiveOverride(_)
+            | Finding::RedundantFile(_)
+            | Finding::EmptyFile(_)
+            | Finding::FileStats(_)
+            | Finding::Provenance(_)
+            | Finding::DeadKey(_)
+            | Finding::SkippedFile(_) => Severity::Info,
+        }
+    }
+
+    /// Whether the finding points out a problem, one that can fail a run,
+    /// rather than describing the files.
+    pub fn is_problem(&self) -> bool {
+        matches!(
+            self,
+            Finding::PointlessOverride(_)
+                | Finding::DuplicateKey(_)
+                | Finding::DeepPath(_)
+                | Finding::TypeMismatch(_)
+                | Finding::NullEmpty(_)
+                | Finding::Conflict(_)
+        )
+    }
+
+    /// File the finding points at.
+    pub fn file(&self) -> &str {
+        match self {
+            Finding::PointlessOverride(override_item) => &override_item.file,
+            Finding::DuplicateKey(warning) => &warning.file,
+            Finding::EffectiveOverride(override_item) => &override_item.file,
+            Finding::DeepPath(warning) => &warning.file,
+            Finding::TypeMismatch(warning) => &warning.file,
+            Finding::NullEmpty(warning) => &warning.file,
+            Finding::Conflict(warning) => &warning.file,
+            Finding::RedundantFile(redundant) => &redundant.file,
+            Finding::RedundantSubtree(subtree) => &subtree.file,
+            Finding::EmptyFile(empty) => &empty.file,
+            Finding::FileStats(stats) => &stats.file,
+            Finding::Provenance(provenance) => &provenance.file,
+            Finding::DeadKey(dead) => &dead.file,
+            Finding::SkippedFile(skipped) => &skipped.file,
+        }
+    }
+
+    pub fn path(&self) -> &[String] {
+        match self {
+            Finding::PointlessOverride(override_item) => &override_item.path,
+            Finding::DuplicateKey(warning) => &warning.path,
+            Finding::EffectiveOverride(override_item) => &override_item.path,
+            Finding::DeepPath(warning) => &warning.path,
+            Finding::TypeMismatch(warning) => &warning.path,
+            Finding::NullEmpty(warning) => &warning.path,
+            Finding::Conflict(warning) => &warning.path,
+            Finding::Provenance(provenance) => &provenance.path,
+            Finding::DeadKey(dead) => &dead.path,
+            Finding::RedundantSubtree(subtree) => &subtree.path,
+            Finding::RedundantFile(_)
+            | Finding::EmptyFile(_)
+            | Finding::FileStats(_)
+            | Finding::SkippedFile(_) => &[],
+        }
+    }
+
+    fn path_mut(&mut self) -> Option<&mut Vec<String>> {
+        match self {
+            Finding::PointlessOverride(override_item) => Some(&mut override_item.path),
+            Finding::DuplicateKey(warning) => Some(&mut warning.path),
+            Finding::EffectiveOverride(override_item) => Some(&mut override_item.path),
+            Finding::DeepPath(warning) => Some(&mut warning.path),
+            Finding::TypeMismatch(warning) => Some(&mut warning.path),
+            Finding::NullEmpty(warning) => Some(&mut warning.path),
+            Finding::Conflict(warning) => Some(&mut warning.path),
+            Finding::Provenance(provenance) => Some(&mut provenance.path),
+            Finding::DeadKey(dead) => Some(&mut dead.path),
+            Finding::RedundantSubtree(subtree) => Some(&mut subtree.path),
+            Finding::RedundantFile(_)
+            | Finding::EmptyFile(_)
+            | Finding::FileStats(_)
+            | Finding::SkippedFile(_) => None,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            Finding::PointlessOverride(override_item) => override_item.line,
+            Finding::DuplicateKey(warning) => warning.second_key_line,
+            Finding::EffectiveOverride(override_item) => override_item.line,
+            Finding::DeepPath(warning) => warning.line,
+            Finding::TypeMismatch(warning) => warning.line,
+            Finding::NullEmpty(warning) => warning.line,
+            Finding::Conflict(warning) => warning.line,
+            Finding::Provenance(provenance) => provenance.line,
+            Finding::DeadKey(dead) => dead.line,
+            Finding::RedundantSubtree(subtree) => subtree.line,
+            Finding::RedundantFile(_)
+            | Finding::EmptyFile(_)
+            | Finding::FileStats(_)
+            | Finding::SkippedFile(_) => 0,
+        }
+    }
+
+    pub fn column(&self) -> usize {
+        match self {
+            Finding::PointlessOverride(override_item) => override_item.column,
+            Finding::DuplicateKey(warning) => warning.second_key_column,
+            Finding::EffectiveOverride(override_item) => override_item.column,
+            Finding::DeepPath(warning) => warning.column,
+            Finding::TypeMismatch(warning) => warning.column,
+            Finding::NullEmpty(warning) => warning.column,
+            Finding::Conflict(warning) => warning.column,
+            Finding::RedundantSubtree(subtree) => subtree.column,
+            Finding::DeadKey(dead) => dead.column,
+            Finding::RedundantFile(_)
+            | Finding::EmptyFile(_)
+            | Finding::FileStats(_)
+            | Finding::Provenance(_)
+            | Finding::SkippedFile(_) => 0,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Finding::PointlessOverride(override_item) => override_item.fmt(f),
+            Finding::DuplicateKey(warning) => warning.fmt(f),
+            Finding::EffectiveOverride(override_item) => override_item.fmt(f),
+            Finding::DeepPath(warning) => warning.fmt(f),
+            Finding::TypeMismatch(warning) => warning.fmt(f),
+            Finding::NullEmpty(warning) => warning.fmt(f),
+            Finding::Conflict(warning) => warning.fmt(f),
+            Finding::RedundantFile(redundant) => redundant.fmt(f),
+            Finding::RedundantSubtree(subtree) => subtree.fmt(f),
+            Finding::EmptyFile(empty) => empty.fmt(f),
+            Finding::FileStats(stats) => stats.fmt(f),
+            Finding::Provenance(provenance) => provenance.fmt(f),
+            Finding::DeadKey(dead) => dead.fmt(f),
+            Finding::SkippedFile(skipped) => skipped.fmt(f),
+        }
+    }
+}
+
+/// Primary key for [`sort_findings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    /// Group by file, then path, then line
+    #[default]
+    File,
+    /// By path, then file, then line
+    Path,
+    /// By line, then file, then path
+    Line,
+}
+
+/// A kind of finding that [`PointlessPointer::only`] can select.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    /// Overrides restating the value of an earlier file
+    Overrides,
+    /// Keys repeated with the same value within a document
+    Duplicates,
+    /// Keys repeated with a different value within a document
+    Warnings,
+    /// Paths whose type changes between layers
+    Types,
+}
+
+/// Put findings in a deterministic order. Files rank in the order they first
+/// appear, which for [`PointlessPointer::findings`] is layer order; the sort
+/// is stable, so findings equal on every key keep their relative order.
+pub fn sort_findings(findings: &mut [Finding], key: SortKey) {
+    let mut files: HashMap<String, usize> = HashMap::new();
+    for finding in findings.iter() {
+        let rank = files.len();
+        files.entry(finding.file().to_string()).or_insert(rank);
+    }
+    let rank = |finding: &Finding| files[finding.file()];
+    match key {
+        SortKey::File => findings.sort_by(|a, b| {
+            (rank(a), a.path(), a.line(), a.column()).cmp(&(
+                rank(b),
+                b.path(),
+                b.line(),
+                b.column(),
+            ))
+        }),
+        SortKey::Path => findings.sort_by(|a, b| {
+            (a.path(), rank(a), a.line(), a.column()).cmp(&(
+                b.path(),
+                rank(b),
+                b.line(),
+                b.column(),
+            ))
+        }),
+        SortKey::Line => findings.sort_by(|a, b| {
+            (a.line(), rank(a), a.path(), a.column()).cmp(&(
+                b.line(),
+                rank(b),
+                b.path(),
+                b.column(),
+            ))
+        }),
+    }
+}
+
+/// Split findings back into the `(overrides, warnings)` shape of `analyze()`.
+fn split_findings(
+    findings: impl Iterator<Item = Finding>,
+) -> (Vec<Override>, Vec<DuplicateKeyWarning>) {
+    let mut pointless = Vec::new();
+    let mut warnings = Vec::new();
+    for finding in findings {
+        match finding {
+            Finding::PointlessOverride(override_item) => pointless.push(override_item),
+            Finding::DuplicateKey(warning) => warnings.push(warning),
+            Finding::EffectiveOverride(_)
+            | Finding::DeepPath(_)
+            | Finding::TypeMismatch(_)
+            | Finding::NullEmpty(_)
+            | Finding::Conflict(_)
+            | Finding::RedundantFile(_)
+            | Finding::RedundantSubtree(_)
+            | Finding::EmptyFile(_)
+            | Finding::FileStats(_)
+            | Finding::Provenance(_)
+            | Finding::DeadKey(_)
+            | Finding::SkippedFile(_) => {}
+        }
+    }
+    (pointless, warnings)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ValueWithLocation {
+    value: String,
+    file: String,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    suppressed: bool, // Marked with a `pointless-pointer: ignore` comment
+    is_null: bool,    // Explicit null, as opposed to an empty string
+    document: usize,  // Index of the `---` document within the file
+    key_line: usize,  // Line of the key this value belongs to
+    key_column: usize,
+    from_alias: bool, // Expanded from an anchor, so the location is the anchor's
+    /// Elements of a scalar-only list, compared one by one like scalars
+    items: Option<Vec<ListItem>>,
+    /// How a scalar was written; lists count as plain
+    #[serde(with = "cache::scalar_style")]
+    style: ScalarStyle,
+    /// The value as rewritten by the value normalizers, when they changed it
+    normalized: Option<String>,
+    value_type: ValueType,
+    /// Kind of each collection along the path, outermost first
+    parents: Vec<ValueType>,
+}
+
+impl ValueWithLocation {
+    /// Columns are reported 1-based with an exclusive end, while the parser
+    /// counts them from 0.
+    fn new(value: String, file: String, start: Marker, end: Marker) -> Self {
+        Self {
+            value,
+            file,
+            line: start.line(),
+            column: start.col() + 1,
+            end_line: end.line(),
+            end_column: end.col() + 1,
+            suppressed: false,
+            is_null: false,
+            document: 0,
+            key_line: start.line(),
+            key_column: start.col() + 1,
+            from_alias: false,
+            items: None,
+            style: ScalarStyle::Plain,
+            normalized: None,
+            value_type: ValueType::String,
+            parents: Vec::new(),
+        }
+    }
+
+    /// Locate the key this value belongs to; without one (a list item or
+    /// document root) the value stands in for it.
+    fn set_key(&mut self, key_start: Option<Marker>) {
+        if let Some(key_start) = key_start {
+            self.key_line = key_start.line();
+            self.key_column = key_start.col() + 1;
+        }
+    }
+
+    /// The text compared against other values.
+    fn compared(&self) -> &str {
+        self.normalized.as_deref().unwrap_or(&self.value)
+    }
+}
+
+/// An element of a scalar-only list, as written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ListItem {
+    Scalar {
+        value: String,
+        /// How the scalar is written back out: plain, quoted and/or tagged
+        rendered: String,
+        is_null: bool,
+    },
+    List(Vec<ListItem>),
+}
+
+impl ListItem {
+    fn scalar(value: &str, style: ScalarStyle, tag: Option<&Tag>) -> Self {
+        let quoted = match style {
+            ScalarStyle::Plain => value.to_string(),
+            ScalarStyle::SingleQuoted => format!("'{}'", value.replace('\'', "''")),
+            _ => serde_json::to_string(value).unwrap_or_default(),
+        };
+        let rendered = match tag {
+            Some(tag) if tag.is_yaml_core_schema() => format!("!!{} {quoted}", tag.suffix),
+            Some(tag) => format!("{}{} {quoted}", tag.handle, tag.suffix),
+            None => quoted,
+        };
+        Self::Scalar {
+            value: value.to_string(),
+            rendered,
+            is_null: normalize::is_null(value, style, tag),
+        }
+    }
+
+    fn render(&self) -> String {
+        match self {
+            Self::Scalar { rendered, .. } => rendered.clone(),
+            Self::List(items) => render_list(items),
+        }
+    }
+
+    /// Written the same way, element by element for nested lists.
+    fn identical(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Scalar { rendered: a, .. }, Self::Scalar { rendered: b, .. }) => a == b,
+            (Self::List(a), Self::List(b)) => lists_identical(a, b),
+            _ => false,
+        }
+    }
+
+    /// Same value once normalized, element by element for nested lists.
+    fn equivalent(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Scalar {
+                    value: a,
+                    is_null: a_null,
+                    ..
+                },
+                Self::Scalar {
+                    value: b,
+                    is_null: b_null,
+                    ..
+                },
+            ) => a_null == b_null && normalize::equivalent(a, b),
+            (Self::List(a), Self::List(b)) => lists_equivalent(a, b),
+            _ => false,
+        }
+    }
+}
+
+fn render_list(items: &[ListItem]) -> String {
+    let rendered: Vec<String> = items.iter().map(ListItem::render).collect();
+    format!("[{}]", rendered.join(", "))
+}
+
+fn lists_equivalent(a: &[ListItem], b: &[ListItem]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.equivalent(b))
+}
+
+fn lists_identical(a: &[ListItem], b: &[ListItem]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.identical(b))
+}
+
+type Entries = Vec<(Vec<String>, ValueWithLocation)>;
+
+/// See [`PointlessPointer::parsed_layers`].
+type ParsedLayers = (Vec<Entries>, Vec<String>, Vec<SkippedFile>, HashSet<String>);
+
+/// YAML merge key; its value's keys are folded into the enclosing mapping.
+const MERGE_KEY: &str = "<<";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKind {
+    Scalar,
+    Sequence,
+    Mapping,
+}
+
+/// The values produced by a finished node, with paths relative to the node.
+#[derive(Debug, Clone)]
+struct Collected {
+    kind: NodeKind,
+    entries: Entries,
+    /// Form used inside a scalar-only list; `None` for mappings and object lists
+    item: Option<ListItem>,
+}
+
+/// A collection the collector is currently inside of.
+#[derive(Debug)]
+enum FrameKind {
+    Mapping {
+        /// Key waiting for its value; `None` means the next scalar is a key
+        pending_key: Option<String>,
+        pending_key_start: Marker,
+        /// Keys defined directly in this mapping, which win over merged ones
+        local_keys: HashSet<String>,
+        /// Entries pulled in through `<<`, relative to this mapping
+        merged: Entries,
+        start: Marker,
+    },
+    Sequence {
+        index: usize,
+        items: Vec<ListItem>, // Used for whole-list comparison
+        has_mapping: bool,    // Once set, per-element entries are kept instead
+        start: Marker,
+        /// Where the last item so far ends
+        last_end: Option<Marker>,
+    },
+}
+
+#[derive(Debug)]
+struct Frame {
+    kind: FrameKind,
+    /// Key or index of this collection in its parent, `None` at the root
+    segment: Option<String>,
+    /// Where the key of this collection starts, if it has one
+    key_start: Option<Marker>,
+    anchor: usize,
+    entries: Entries, // Relative to this collection
+}
+
+/// Builds the path of every value from the parser's events. Paths follow
+/// the collection frames rather than indentation, so flow collections such
+/// as `{limits: {cpu: 1}}` or `[80, 443]` produce exactly the entries and list
+/// renderings of their block equivalents.
+///
+/// The parser's events are balanced, which keeps these invariants; debug
+/// builds, such as the fuzz target in `fuzz/`, assert them:
+/// - `stack` has a frame for each collection open in the current document,
+///   innermost last. An end event pops the frame of the same kind that its
+///   start pushed, so it never finds the stack empty, and the stack is empty
+///   again when the document ends.
+/// - A mapping frame's `pending_key` is `None` exactly when its next node is
+///   a key.
+/// - Only whole top-level nodes reach `values`, delivered with the stack
+///   empty; everything else goes to the entries of its parent frame.
+struct YamlValueCollector {
+    values: Entries, // Using Vec to preserve order and handle duplicates
+    current_file: String,
+    stack: Vec<Frame>,
+    anchors: HashMap<usize, Collected>,
+    document: usize,
+    documents_seen: usize,
+    /// Start of the key consumed by the last `next_segment` call, if any
+    last_key_start: Option<Marker>,
+    /// Lines of keys whose value is a collection, by document and path
+    collection_key_lines: HashMap<(usize, Vec<String>), usize>,
+    /// 1 traces every event to stderr, 2 also every value recorded
+    verbosity: u8,
+    /// Set when aliases may refer to anchors of lower layers
+    shared: Option<SharedAnchors>,
+}
+
+/// What the collector needs to resolve aliases against anchors defined in
+/// lower layers, which the parser rejects since it sees one file at a time.
+/// Such aliases are replaced by a `~` placeholder before parsing.
+#[derive(Default)]
+struct SharedAnchors {
+    /// The text being parsed, to read anchor names from
+    text: Vec<char>,
+    /// Where the previous event ended; the anchor of the next node is
+    /// between there and the node
+    last_end: usize,
+    /// Names of this file's anchors, by the parser's id for them
+    names: HashMap<usize, String>,
+    /// Names of the aliases the placeholders stand for, by where they start
+    placeholders: HashMap<usize, String>,
+    /// Anchors of lower layers, by name
+    lower: HashMap<String, Collected>,
+    /// Aliases that no layer has an anchor for, with their lines
+    unresolved: Vec<(usize, String)>,
+}
+
+impl SharedAnchors {
+    /// The name of the `&anchor` in front of a node starting at `start`,
+    /// skipping comments on the way.
+    fn anchor_name(&self, start: usize) -> String {
+        let region = &self.text[self.last_end.min(start)..start];
+        let mut chars = region.iter().enumerate();
+        while let Some((index, &c)) = chars.next() {
+            match c {
+                '#' if index == 0 || region[index - 1].is_whitespace() => {
+                    chars.by_ref().find(|&(_, &c)| c == '\n');
+                }
+                '&' => {
+                    return region[index + 1..]
+                        .iter()
+                        .take_while(|&&c| !ends_alias_name(c))
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+        String::new()
+    }
+}
+
+/// Whether `c` ends an anchor or alias name.
+fn ends_alias_name(c: char) -> bool {
+    c.is_whitespace() || matches!(c, ',' | '[' | ']' | '{' | '}')
+}
+
+/// Aliases in `text` to anchors that don't come before them in the same
+/// document, as where the `*` is and the name. The scan only knows where a
+/// node can start, skipping comments, quoted scalars and block scalars, so
+/// it may also take a `*` inside a plain scalar for an alias; the parser
+/// then shows which ones weren't.
+fn unknown_aliases(text: &[char]) -> Vec<(usize, String)> {
+    let name_at = |start: usize| -> String {
+        text[start..]
+            .iter()
+            .take_while(|&&c| !ends_alias_name(c))
+            .collect()
+    };
+    let followed_by_space = |index: usize| text.get(index + 1).is_none_or(|c| c.is_whitespace());
+    let mut anchors = HashSet::new();
+    let mut unknown = Vec::new();
+    let mut index = 0;
+    let mut line_start = true;
+    // Whether the next character that isn't a space can start a node
+    let mut node_start = true;
+    let mut indent = 0;
+    // Indentation of the line a block scalar's indicator is on
+    let mut block_scalar = None;
+    while index < text.len() {
+        if line_start {
+            line_start = false;
+            let spaces = text[index..].iter().take_while(|&&c| c == ' ').count();
+            let blank = text
+                .get(index + spaces)
+                .is_none_or(|&c| c == '\n' || c == '\r');
+            if let Some(parent) = block_scalar {
+                if blank || spaces > parent {
+                    index += text[index..].iter().take_while(|&&c| c != '\n').count();
+                    continue;
+                }
+                block_scalar = None;
+            }
+            indent = spaces;
+            index += spaces;
+            node_start = true;
+            let marker: String = text[index..].iter().take(3).collect();
+            if spaces == 0 && (marker == "---" || marker == "...") && followed_by_space(index + 2) {
+                anchors.clear();
+                index += 3;
+            }
+            continue;
+        }
+        let c = text[index];
+        match c {
+            '\n' => {
+                line_start = true;
+                index += 1;
+            }
+            _ if c.is_whitespace() => index += 1,
+            '#' if index == 0 || text[index - 1].is_whitespace() => {
+                index += text[index..].iter().take_while(|&&c| c != '\n').count();
+            }
+            '*' if node_start => {
+                let name = name_at(index + 1);
+                let length = 1 + name.chars().count();
+                if !anchors.contains(&name) {
+                    unknown.push((index, name));
+                }
+                index += length;
+                node_start = false;
+            }
+            '&' | '!' if node_start => {
+                let name = name_at(index + 1);
+                index += 1 + name.chars().count();
+                if c == '&' {
+                    anchors.insert(name);
+                }
+            }
+            '\'' | '"' if node_start => {
+                index += 1;
+                while let Some(&next) = text.get(index) {
+                    index += 1;
+                    match next {
+                        '\\' if c == '"' => index += 1,
+                        '\'' if c == '\'' && text.get(index) == Some(&'\'') => index += 1,
+                        _ if next == c => break,
+                        _ => {}
+                    }
+                }
+                node_start = false;
+            }
+            '|' | '>' if node_start => {
+                block_scalar = Some(indent);
+                index += text[index..].iter().take_while(|&&c| c != '\n').count();
+            }
+            '-' | '?' | ':' if followed_by_space(index) => {
+                index += 1;
+                node_start = true;
+            }
+            '[' | '{' | ',' => {
+                index += 1;
+                node_start = true;
+            }
+            _ => {
+                index += 1;
+                node_start = false;
+            }
+        }
+    }
+    unknown
+}
+
+impl YamlValueCollector {
+    fn new(file: String, verbosity: u8) -> Self {
+        Self {
+            values: Vec::new(),
+            current_file: file,
+            stack: Vec::new(),
+            anchors: HashMap::new(),
+            document: 0,
+            documents_seen: 0,
+            last_key_start: None,
+            collection_key_lines: HashMap::new(),
+            verbosity,
+            shared: None,
+        }
+    }
+
+    /// This file's anchors by name, for the layers above it. A name used
+    /// twice is the later anchor.
+    fn named_anchors(&mut self) -> Vec<(String, Collected)> {
+        let Some(shared) = &self.shared else {
+            return Vec::new();
+        };
+        let mut ids: Vec<&usize> = self.anchors.keys().collect();
+        ids.sort();
+        ids.into_iter()
+            .filter_map(|id| Some((shared.names.get(id)?.clone(), self.anchors[id].clone())))
+            .collect()
+    }
+
+    /// Put the value of a lower layer's anchor where a placeholder stands
+    /// for an alias to it, located at the alias since that is where this
+    /// file sets it. Aliases no layer has an anchor for are left out.
+    fn deliver_shared(&mut self, segment: Option<String>, name: &str, span: Span) {
+        let Some(shared) = &mut self.shared else {
+            return;
+        };
+        let Some(mut collected) = shared.lower.get(name).cloned() else {
+            shared
+                .unresolved
+                .push((span.start.line(), name.to_string()));
+            return;
+        };
+        for (_, value) in &mut collected.entries {
+            let located = ValueWithLocation::new(
+                String::new(),
+                self.current_file.clone(),
+                span.start,
+                span.end,
+            );
+            value.file = located.file;
+            value.line = located.line;
+            value.column = located.column;
+            value.end_line = located.end_line;
+            value.end_column = located.end_column;
+            value.key_line = located.key_line;
+            value.key_column = located.key_column;
+            value.from_alias = true;
+        }
+        self.deliver(segment, collected, span.end);
+    }
+
+    /// Where the collector stands after an event: the path of the innermost
+    /// open collection and what it expects next.
+    fn trace(&self, event: &str, span: Span) {
+        let path: Vec<String> = self
+            .stack
+            .iter()
+            .filter_map(|frame| frame.segment.clone())
+            .collect();
+        let state = match self.stack.last().map(|frame| &frame.kind) {
+            None => "at document root".to_string(),
+            Some(FrameKind::Mapping {
+                pending_key: None, ..
+            }) => "mapping, expecting key".to_string(),
+            Some(FrameKind::Mapping {
+                pending_key: Some(key),
+                ..
+            }) => format!("mapping, key `{key}` awaiting value"),
+            Some(FrameKind::Sequence { index, .. }) => format!("sequence, next item {index}"),
+        };
+        eprintln!(
+            "{}:{}:{}: {event} -> path `{}`, depth {}, {state}",
+            self.current_file,
+            span.start.line(),
+            span.start.col() + 1,
+            format_path(&path),
+            self.stack.len()
+        );
+    }
+
+    /// Path segment for the value about to start, consuming the pending key
+    /// or advancing the sequence index. `None` at the document root.
+    fn next_segment(&mut self) -> Option<String> {
+        self.last_key_start = None;
+        match &mut self.stack.last_mut()?.kind {
+            FrameKind::Mapping {
+                pending_key,
+                pending_key_start,
+                ..
+            } => match pending_key.take() {
+                Some(key) => {
+                    self.last_key_start = Some(*pending_key_start);
+                    Some(key)
+                }
+                None => {
+                    // A collection used as a key; track it under a placeholder and
+                    // leave the key pending so its value still gets a slot
+                    *pending_key = Some("?".to_string());
+                    Some("?".to_string())
+                }
+            },
+            FrameKind::Sequence { index, .. } => {
+                let segment = index.to_string();
+                *index += 1;
+                Some(segment)
+            }
+        }
+    }
+
+    fn start_collection(&mut self, kind: FrameKind, anchor: usize) {
+        let segment = self.next_segment();
+        let key_start = self.last_key_start;
+        if let Some(key_start) = key_start {
+            let path: Vec<String> = self
+                .stack
+                .iter()
+                .filter_map(|frame| frame.segment.clone())
+                .chain(segment.clone())
+                .collect();
+            self.collection_key_lines
+                .insert((self.document, path), key_start.line());
+        }
+        self.stack.push(Frame {
+            kind,
+            segment,
+            key_start,
+            anchor,
+            entries: Vec::new(),
+        });
+    }
+
+    fn finish_collection(&mut self, frame: Frame, span: Span) {
+        // A block sequence has no closing token and ends where the next token
+        // starts, maybe lines further down, so it ends with its last item
+        let end = match frame.kind {
+            FrameKind::Sequence {
+                last_end: Some(last_end),
+                ..
+            } if span.start.index() == span.end.index() => last_end,
+            _ => span.end,
+        };
+        let collected = match frame.kind {
+            FrameKind::Mapping {
+                local_keys,
+                merged,
+                start,
+                ..
+            } => {
+                // Local keys win over merged ones
+                let mut entries: Entries = merged
+                    .into_iter()
+                    .filter(|(path, _)| !local_keys.contains(&path[0]))
+                    .collect();
+                entries.extend(frame.entries);
+                // With nothing inside, the empty mapping itself is the value,
+                // so `{}` can be compared like an empty list's `[]`
+                if entries.is_empty() {
+                    let mut value = ValueWithLocation::new(
+                        "{}".to_string(),
+                        self.current_file.clone(),
+                        start,
+                        end,
+                    );
+                    value.set_key(frame.key_start);
+                    value.value_type = ValueType::Mapping;
+                    entries.push((vec![], value));
+                }
+                Collected {
+                    kind: NodeKind::Mapping,
+                    entries,
+                    item: None,
+                }
+            }
+            FrameKind::Sequence {
+                has_mapping: true, ..
+            } => {
+                // List of objects - keep the per-field entries
+                Collected {
+                    kind: NodeKind::Sequence,
+                    entries: frame.entries,
+                    item: None,
+                }
+            }
+            FrameKind::Sequence { items, start, .. } => {
+                // Scalar-only list - record the entire sequence as one value
+                let mut value = ValueWithLocation::new(
+                    render_list(&items),
+                    self.current_file.clone(),
+                    start,
+                    end,
+                );
+                value.set_key(frame.key_start);
+                value.items = Some(items.clone());
+                value.value_type = ValueType::Sequence;
+                Collected {
+                    kind: NodeKind::Sequence,
+                    entries: vec![(vec![], value)],
+                    item: Some(ListItem::List(items)),
+                }
+            }
+        };
+        if frame.anchor != 0 {
+            self.anchors.insert(frame.anchor, collected.clone());
+        }
+        self.deliver(frame.segment, collected, end);
+    }
+
+    /// Hand a finished node to the enclosing collection (or the document).
+    fn deliver(&mut self, segment: Option<String>, node: Collected, end: Marker) {
+        let Some(parent) = self.stack.last_mut() else {
+            if self.verbosity >= 2 {
+                for (path, value) in &node.entries {
+                    eprintln!(
+                        "{}:{}: recorded `{}` = {}",
+                        self.current_file,
+                        value.line,
+                        format_path(path),
+                        value.value
+                    );
+                }
+            }
+            let document = self.document;
+            self.values
+                .extend(node.entries.into_iter().map(|(path, mut value)| {
+                    value.document = document;
+                    (path, value)
+                }));
+            return;
+        };
+        // Anything inside a collection has a segment
+        let segment = segment.unwrap_or_default();
+        let parent_type = match &mut parent.kind {
+            FrameKind::Mapping { merged, .. } if segment == MERGE_KEY => {
+                merge_into(merged, node);
+                return;
+            }
+            FrameKind::Mapping { .. } => ValueType::Mapping,
+            FrameKind::Sequence {
+                items,
+                has_mapping,
+                last_end,
+                ..
+            } => {
+                *last_end = Some(end);
+                match &node.item {
+                    Some(item) => items.push(item.clone()),
+                    None => *has_mapping = true,
+                }
+                ValueType::Sequence
+            }
+        };
+        parent
+            .entries
+            .extend(node.entries.into_iter().map(|(path, mut value)| {
+                let mut full = Vec::with_capacity(path.len() + 1);
+                full.push(segment.clone());
+                full.extend(path);
+                value.parents.insert(0, parent_type);
+                (full, value)
+            }));
+    }
+}
+
+/// Fold a `<<` value into a mapping's merged entries. The value is either a
+/// mapping or a sequence of mappings, where earlier mappings take precedence.
+fn merge_into(merged: &mut Entries, node: Collected) {
+    let taken: HashSet<String> = merged.iter().map(|(path, _)| path[0].clone()).collect();
+    let mut sources: Vec<(String, Entries)> = Vec::new();
+    for (mut path, mut value) in node.entries {
+        let source = if node.kind == NodeKind::Sequence && !path.is_empty() {
+            value.parents.remove(0);
+            path.remove(0)
+        } else {
+            String::new()
+        };
+        if path.is_empty() || taken.contains(&path[0]) {
+            continue;
+        }
+        match sources.iter_mut().find(|(name, _)| *name == source) {
+            Some((_, entries)) => entries.push((path, value)),
+            None => sources.push((source, vec![(path, value)])),
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for (_, entries) in sources {
+        let keys: HashSet<String> = entries.iter().map(|(path, _)| path[0].clone()).collect();
+        merged.extend(
+            entries
+                .into_iter()
+                .filter(|(path, _)| !seen.contains(&path[0])),
+        );
+        seen.extend(keys);
+    }
+}
+
+impl<'input> SpannedEventReceiver<'input> for YamlValueCollector {
+    fn on_event(&mut self, event: Event<'input>, span: Span) {
+        let traced = (self.verbosity >= 1).then(|| format!("{event:?}"));
+        self.handle_event(event, span);
+        if let Some(event) = traced {
+            self.trace(&event, span);
+        }
+    }
+}
+
+impl YamlValueCollector {
+    fn handle_event(&mut self, event: Event<'_>, span: Span) {
+        if let Some(shared) = &mut self.shared {
+            if let Event::MappingStart(anchor, _)
+            | Event::SequenceStart(anchor, _)
+            | Event::Scalar(_, _, anchor, _) = event
+                && anchor != 0
+            {
+                let name = shared.anchor_name(span.start.index());
+                shared.names.insert(anchor, name);
+            }
+            shared.last_end = span.end.index();
+        }
+        match event {
+            Event::MappingStart(anchor, _) => {
+                let kind = FrameKind::Mapping {
+                    pending_key: None,
+                    pending_key_start: Marker::default(),
+                    local_keys: HashSet::new(),
+                    merged: Vec::new(),
+                    start: span.start,
+                };
+                self.start_collection(kind, anchor);
+            }
+            Event::SequenceStart(anchor, _) => {
+                let kind = FrameKind::Sequence {
+                    index: 0,
+                    items: Vec::new(),
+                    has_mapping: false,
+                    start: span.start,
+                    last_end: None,
+                };
+                self.start_collection(kind, anchor);
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                let frame = self.stack.pop();
+                debug_assert!(
+                    matches!(
+                        (&event, frame.as_ref().map(|frame| &frame.kind)),
+                        (Event::MappingEnd, Some(FrameKind::Mapping { .. }))
+                            | (Event::SequenceEnd, Some(FrameKind::Sequence { .. }))
+                    ),
+                    "{:?} at {}:{} doesn't close the innermost collection",
+                    event,
+                    self.current_file,
+                    span.start.line()
+                );
+                if let Some(frame) = frame {
+                    self.finish_collection(frame, span);
+                }
+            }
+            Event::Scalar(value, style, anchor, tag) => {
+                if let Some(Frame {
+                    kind:
+                        FrameKind::Mapping {
+                            pending_key,
+                            pending_key_start,
+                            local_keys,
+                            ..
+                        },
+                    ..
+                }) = self.stack.last_mut()
+                    && pending_key.is_none()
+                {
+                    // This is a key
+                    if value != MERGE_KEY {
+                        local_keys.insert(value.to_string());
+                    }
+                    *pending_key = Some(value.into_owned());
+                    *pending_key_start = span.start;
+                    return;
+                }
+
+                let segment = self.next_segment();
+                if let Some(name) = self
+                    .shared
+                    .as_mut()
+                    .and_then(|shared| shared.placeholders.remove(&span.start.index()))
+                {
+                    self.deliver_shared(segment, &name, span);
+                    return;
+                }
+                // The value and span are the scalar's alone, without an anchor
+                // in front of it (`&t 30`), so it compares like a plain `30`
+                let mut location = ValueWithLocation::new(
+                    value.to_string(),
+                    self.current_file.clone(),
+                    span.start,
+                    span.end,
+                );
+                location.is_null = normalize::is_null(&value, style, tag.as_deref());
+                location.style = style;
+                location.value_type = normalize::value_type(&value, style, tag.as_deref());
+                location.set_key(self.last_key_start);
+                let collected = Collected {
+                    kind: NodeKind::Scalar,
+                    item: Some(ListItem::scalar(&value, style, tag.as_deref())),
+                    entries: vec![(vec![], location)],
+                };
+                if anchor != 0 {
+                    self.anchors.insert(anchor, collected.clone());
+                }
+                self.deliver(segment, collected, span.end);
+            }
+            Event::Alias(anchor) => {
+                let segment = self.next_segment();
+                if let Some(mut collected) = self.anchors.get(&anchor).cloned() {
+                    for (_, value) in &mut collected.entries {
+                        value.from_alias = true;
+                    }
+                    self.deliver(segment, collected, span.end);
+                }
+            }
+            Event::DocumentStart(_) => {
+                // Documents are independent; start from a clean slate
+                self.stack.clear();
+                self.document = self.documents_seen;
+                self.documents_seen += 1;
+            }
+            Event::DocumentEnd => debug_assert!(
+                self.stack.is_empty(),
+                "{}:{}: document ends inside a collection",
+                self.current_file,
+                span.start.line()
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Where quantity comparison applies unless configured otherwise: container
+/// `resources` at any depth.
+pub const DEFAULT_QUANTITY_PATHS: &[&str] = &["resources.*", "*.resources.*"];
+
+pub struct PointlessPointer {
+    base_file: PathBuf,
+    override_files: Vec<PathBuf>,
+    defaults_file: Option<PathBuf>,
+    options: CompareOptions,
+    jobs: Option<usize>,
+    show_effective: bool,
+    stats: bool,
+    provenance: bool,
+    rollup: bool,
+    timings: Timings,
+    warn_null_empty: bool,
+    warn_conflicts: bool,
+    dead_keys: bool,
+    max_depth: Option<usize>,
+    /// Paths whose values compare as Kubernetes quantities
+    quantity_paths: Vec<Pattern>,
+    /// Lists of mappings whose elements match up by a field, not position
+    list_merge_keys: Vec<(Pattern, String)>,
+    /// Classes of values that compare equal, at paths matching the glob
+    equivalences: Vec<(Pattern, Vec<String>)>,
+    /// Layers whose keys may repeat without being reported
+    no_dup_check: Vec<Pattern>,
+    /// Paths whose findings are left out
+    ignore: IgnorePatterns,
+    /// Findings the last analysis left out for `ignore`
+    suppressed: Mutex<usize>,
+    /// Path whose subtree alone is compared; everything when empty
+    root: Vec<String>,
+    /// Show paths relative to `root`
+    relative_to_root: bool,
+    /// Values of the `--set` layer on top of the files
+    set_values: Entries,
+    skip_missing: bool,
+    include_root_scalars: bool,
+    cache: Option<Cache>,
+    /// Normalizers given by the caller, run after the built-in ones
+    key_normalizer: Option<Box<dyn KeyNormalizer>>,
+    value_normalizer: Option<Box<dyn ValueNormalizer>>,
+    /// Format of layers whose name doesn't tell
+    input_format: Option<InputFormat>,
+    timeout: Option<Duration>,
+    verbosity: u8,
+    progress: bool,
+    stdin_multidoc: bool,
+    /// Stdin's text under `stdin_multidoc`, kept once read so that
+    /// [`labels`](Self::labels) and the analysis split the same documents
+    stdin: OnceLock<String>,
+    /// Layers given as text by [`from_sources`](Self::from_sources), used in
+    /// place of the files
+    sources: Option<Vec<SourceFile>>,
+}
+
+/// Knobs for how values are compared once collected.
+#[derive(Debug, Clone, Copy, Default)]
+struct CompareOptions {
+    strict_strings: bool,
+    null_deletes: bool,
+    case_insensitive_keys: bool,
+    ignore_list_order: bool,
+    expand_env: bool,
+    track_chain: bool,
+    reverse_precedence: bool,
+    pairwise: bool,
+    skip_overrides: bool,
+    skip_duplicates: bool,
+    skip_warnings: bool,
+    skip_types: bool,
+    float_epsilon: f64,
+}
+
+impl PointlessPointer {
+    /// Start setting up an analyzer one layer and option at a time.
+    pub fn builder() -> PointlessPointerBuilder {
+        PointlessPointerBuilder::new()
+    }
+
+    /// An analyzer of `override_files`, in priority order, above
+    /// `base_file`, with every option at its default. The same as
+    /// [`builder`](Self::builder) given just these files.
+    pub fn new(base_file: PathBuf, override_files: Vec<PathBuf>) -> Self {
+        Self {
+            base_file,
+            override_files,
+            defaults_file: None,
+            options: CompareOptions::default(),
+            jobs: None,
+            show_effective: false,
+            stats: false,
+            provenance: false,
+            rollup: false,
+            timings: Timings::default(),
+            warn_null_empty: false,
+            warn_conflicts: false,
+            dead_keys: false,
+            max_depth: None,
+            quantity_paths: DEFAULT_QUANTITY_PATHS
+                .iter()
+                .map(|pattern| Pattern::new(pattern).expect("default patterns are valid"))
+                .collect(),
+            list_merge_keys: Vec::new(),
+            equivalences: Vec::new(),
+            no_dup_check: Vec::new(),
+            ignore: IgnorePatterns::default(),
+            suppressed: Mutex::new(0),
+            root: Vec::new(),
+            relative_to_root: false,
+            set_values: Vec::new(),
+            skip_missing: false,
+            include_root_scalars: false,
+            cache: None,
+            key_normalizer: None,
+            value_normalizer: None,
+            input_format: None,
+            timeout: None,
+            verbosity: 0,
+            progress: false,
+            stdin_multidoc: false,
+            stdin: OnceLock::new(),
+            sources: None,
+        }
+    }
+
+    /// Analyze in-memory YAML instead of files: `(label, content)` pairs in
+    /// priority order, the base first. Findings refer to each layer by its
+    /// label. Nothing is read from disk, so [`defaults`](Self::defaults) has
+    /// no effect.
+    pub fn from_sources(sources: Vec<(String, String)>) -> Self {
+        let mut analyzer = Self::new(PathBuf::new(), Vec::new());
+        analyzer.sources = Some(
+            sources
+                .into_iter()
+                .map(|(label, content)| SourceFile { label, content })
+                .collect(),
+        );
+        analyzer
+    }
+
+    /// Chart defaults to treat as the lowest-priority layer, below the base file.
+    pub fn defaults(mut self, defaults_file: Option<PathBuf>) -> Self {
+        self.defaults_file = defaults_file;
+        self
+    }
+
+    /// Compare values as exact strings instead of by their YAML type, so
+    /// `1` and `1.0` or `yes` and `true` count as different. The same text
+    /// quoted and unquoted differs too when quoting changes its type, as
+    /// `"1.10"` (a string) and `1.10` (a float) do.
+    pub fn strict_strings(mut self, strict: bool) -> Self {
+        self.options.strict_strings = strict;
+        self
+    }
+
+    /// Treat numbers as equal when they are at most `epsilon` apart and at
+    /// least one of them is a float, so `0.5` and `0.50000001` can count as
+    /// the same. Only numbers typed as such take part: quoted numbers are
+    /// strings, and nothing applies under
+    /// [`strict_strings`](Self::strict_strings). NaN equals only NaN. The
+    /// default of 0 compares floats exactly.
+    ///
+    /// ```
+    /// use pointless_pointer::PointlessPointer;
+    ///
+    /// let pointless = |base: &str, over: &str, epsilon| {
+    ///     let analyzer = PointlessPointer::from_sources(vec![
+    ///         ("base".into(), format!("cpu: {base}\n")),
+    ///         ("prod".into(), format!("cpu: {over}\n")),
+    ///     ])
+    ///     .float_epsilon(epsilon);
+    ///     analyzer.analyze().unwrap().0.len() == 1
+    /// };
+    /// assert!(!pointless("0.5", "0.50000001", 0.0));
+    /// assert!(pointless("0.5", "0.50000001", 1e-6));
+    /// ```
+    pub fn float_epsilon(mut self, epsilon: f64) -> Self {
+        self.options.float_epsilon = epsilon;
+        self
+    }
+
+    /// Treat null overrides as intentional deletions: a value set to `null`
+    /// is never reported as pointless, even when it was already null.
+    pub fn null_deletes(mut self, null_deletes: bool) -> Self {
+        self.options.null_deletes = null_deletes;
+        self
+    }
+
+    /// Match keys regardless of case, so `Foo` in one file and `foo` in
+    /// another are the same path. Findings keep each file's own casing. A
+    /// file that deliberately uses both casings of a key will see them
+    /// collide as duplicates.
+    pub fn case_insensitive_keys(mut self, case_insensitive: bool) -> Self {
+        self.options.case_insensitive_keys = case_insensitive;
+        self
+    }
+
+    /// Compare scalar-only lists as multisets, so an override that merely
+    /// reorders a list is pointless. Without this such overrides are kept,
+    /// and flagged [`reorder_only`](EffectiveOverride::reorder_only).
+    pub fn ignore_list_order(mut self, ignore: bool) -> Self {
+        self.options.ignore_list_order = ignore;
+        self
+    }
+
+    /// Expand `${VAR}` placeholders in scalar values from the environment
+    /// before comparing, so restating an env-derived value is pointless.
+    /// Unset variables are compared as written and warned about on stderr.
+    pub fn expand_env(mut self, expand: bool) -> Self {
+        self.options.expand_env = expand;
+        self
+    }
+
+    /// Match keys by the form `normalizer` gives them, after
+    /// [`case_insensitive_keys`](Self::case_insensitive_keys) if that is on.
+    pub fn key_normalizer(mut self, normalizer: impl KeyNormalizer + 'static) -> Self {
+        self.key_normalizer = Some(Box::new(normalizer));
+        self
+    }
+
+    /// Compare scalar values by the form `normalizer` gives them, after
+    /// [`expand_env`](Self::expand_env) if that is on. Only overrides and
+    /// duplicates use it; type changes go by the values as written.
+    pub fn value_normalizer(mut self, normalizer: impl ValueNormalizer + 'static) -> Self {
+        self.value_normalizer = Some(Box::new(normalizer));
+        self
+    }
+
+    /// Record on each override every earlier layer that set its path, so
+    /// findings can show the whole chain, such as a value restored after an
+    /// intermediate layer changed it.
+    pub fn show_chain(mut self, show_chain: bool) -> Self {
+        self.options.track_chain = show_chain;
+        self
+    }
+
+    /// Let earlier files win instead of later ones, so the base is checked
+    /// for values an override already guarantees. Findings then name the
+    /// later file as the one a value repeats.
+    pub fn reverse_precedence(mut self, reverse: bool) -> Self {
+        self.options.reverse_precedence = reverse;
+        self
+    }
+
+    /// Compare each file with the one just before it rather than with
+    /// everything before it, so a value is only pointless when the previous
+    /// layer itself sets it the same way.
+    pub fn pairwise(mut self, pairwise: bool) -> Self {
+        self.options.pairwise = pairwise;
+        self
+    }
+
+    /// Only look for the given kinds of finding; all of them when empty.
+    /// Without [`Category::Overrides`] there are no effective overrides
+    /// either, and without it or [`Category::Types`] files aren't compared
+    /// with each other at all.
+    pub fn only(mut self, categories: &[Category]) -> Self {
+        let skip = |category| !categories.is_empty() && !categories.contains(&category);
+        self.options.skip_overrides = skip(Category::Overrides);
+        self.options.skip_duplicates = skip(Category::Duplicates);
+        self.options.skip_warnings = skip(Category::Warnings);
+        self.options.skip_types = skip(Category::Types);
+        self
+    }
+
+    /// Dotted-path globs whose values are compared as Kubernetes quantities,
+    /// so `500m` equals `0.5` and `512Mi` equals `536870912`. Defaults to
+    /// [`DEFAULT_QUANTITY_PATHS`]; an empty list turns this off.
+    pub fn quantity_paths(mut self, patterns: &[String]) -> Result<Self> {
+        self.quantity_paths = patterns
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).map_err(|source| AnalyzeError::Pattern {
+                    pattern: pattern.clone(),
+                    source,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    /// Globs for layers, such as `generated/*.yaml`, whose repeated keys
+    /// aren't reported: neither as duplicates nor as warnings. Their values
+    /// are still compared with other layers, the last of a repeated key
+    /// being the one in effect.
+    pub fn no_dup_check(mut self, patterns: &[String]) -> Result<Self> {
+        self.no_dup_check = patterns
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).map_err(|source| AnalyzeError::Pattern {
+                    pattern: pattern.clone(),
+                    source,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    /// Leave out findings at paths matching any of `patterns`: dotted-path
+    /// globs such as `image.*`, or exact paths in canonical form such as
+    /// `$.annotations["example.com/team"]`. Paths are matched as findings
+    /// show them, so relative to [`root`](Self::root) when
+    /// [`relative_to_root`](Self::relative_to_root) is on.
+    pub fn ignore_paths(mut self, patterns: &[String]) -> Result<Self> {
+        self.ignore = IgnorePatterns::parse(patterns)?;
+        Ok(self)
+    }
+
+    /// How many findings the last analysis left out for matching
+    /// [`ignore_paths`](Self::ignore_paths).
+    pub fn suppressed(&self) -> usize {
+        *self
+            .suppressed
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Match the elements of lists of mappings by a field instead of by
+    /// position, like Kubernetes strategic merge: with `("*env", "name")` an
+    /// `env` entry `{name: FOO, value: bar}` is at `env.name=FOO.value`
+    /// rather than `env.0.value`, whatever its index. Patterns are dotted-path
+    /// globs for the list; elements without the field keep their index.
+    pub fn list_merge_keys(mut self, keys: &[(String, String)]) -> Result<Self> {
+        self.list_merge_keys = keys
+            .iter()
+            .map(|(pattern, field)| {
+                let pattern = Pattern::new(pattern).map_err(|source| AnalyzeError::Pattern {
+                    pattern: pattern.clone(),
+                    source,
+                })?;
+                Ok((pattern, field.clone()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    /// Classes of values to treat as the same, such as `Always`, `always`
+    /// and `ALWAYS`, each at paths matching a dotted-path glob (`*` for
+    /// anywhere). Values are matched as written, after
+    /// [`expand_env`](Self::expand_env).
+    pub fn equivalent_values(mut self, classes: &[(String, Vec<String>)]) -> Result<Self> {
+        self.equivalences = classes
+            .iter()
+            .filter(|(_, class)| !class.is_empty())
+            .map(|(pattern, class)| {
+                let pattern = Pattern::new(pattern).map_err(|source| AnalyzeError::Pattern {
+                    pattern: pattern.clone(),
+                    source,
+                })?;
+                Ok((pattern, class.clone()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    /// Values given like Helm's `--set` (`image.tag=v2`, `ports[0]=80`,
+    /// `hosts={a,b}`, several at once separated by commas), applied as one
+    /// layer above every file and labelled [`SET_LABEL`]. Each expression is
+    /// a line of that layer.
+    pub fn set_values(mut self, expressions: &[String]) -> Result<Self> {
+        let mut values = Vec::new();
+        for (index, expression) in expressions.iter().enumerate() {
+            let parsed = set::parse(SET_LABEL, expression, index + 1).map_err(|reason| {
+                AnalyzeError::Set {
+                    expression: expression.clone(),
+                    reason,
+                }
+            })?;
+            values.extend(parsed);
+        }
+        self.set_values = values;
+        Ok(self)
+    }
+
+    /// Also report overrides that do change a value, as
+    /// [`Finding::EffectiveOverride`]. `analyze()` never returns these.
+    pub fn show_effective(mut self, show_effective: bool) -> Self {
+        self.show_effective = show_effective;
+        self
+    }
+
+    /// Report every value whose path has more than `max_depth` segments as
+    /// a [`Finding::DeepPath`], independently of the override checks.
+    /// `analyze()` never returns these.
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Also report, as a [`Finding::FileStats`] for each layer above the
+    /// lowest, how many paths it sets and how many of those are pointless.
+    /// `analyze()` never returns these.
+    pub fn stats(mut self, stats: bool) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Also report, as a [`Finding::Provenance`] for every path, the layer
+    /// whose value is in effect and how many times the path is set.
+    pub fn provenance(mut self, provenance: bool) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Print to stderr, after each analysis, the wall time spent reading,
+    /// parsing each layer, comparing and on the remaining checks.
+    pub fn timings(mut self, timings: bool) -> Self {
+        self.timings.enabled = timings;
+        self
+    }
+
+    /// Also report, as a [`Finding::RedundantSubtree`], each mapping or list
+    /// of a layer whose values, two or more, are all pointless overrides of
+    /// earlier layers. Only the outermost such subtree is reported; the
+    /// overrides under it are still reported too, for callers that act on
+    /// them one by one.
+    pub fn rollup(mut self, rollup: bool) -> Self {
+        self.rollup = rollup;
+        self
+    }
+
+    /// Also report, as a [`Finding::NullEmpty`], a null replaced by an empty
+    /// string in a later layer, or the other way round.
+    pub fn warn_null_empty(mut self, warn: bool) -> Self {
+        self.warn_null_empty = warn;
+        self
+    }
+
+    /// Also report, as a [`Finding::Conflict`], each value an override file
+    /// sets differently from an earlier override file. Only override files
+    /// take part: the base, the defaults and the
+    /// [`set_values`](Self::set_values) layer sit below or above them on
+    /// purpose.
+    pub fn warn_conflicts(mut self, warn: bool) -> Self {
+        self.warn_conflicts = warn;
+        self
+    }
+
+    /// Also report, as a [`Finding::DeadKey`], each value of the lowest
+    /// layer that no layer above it sets, replaces or sets anything under.
+    /// Nothing is reported when there is only one layer.
+    pub fn dead_keys(mut self, dead_keys: bool) -> Self {
+        self.dead_keys = dead_keys;
+        self
+    }
+
+    /// Keep each layer's parsed values under `dir`, keyed by a hash of the
+    /// file's content, so unchanged files aren't parsed again on the next
+    /// run. Files are then read whole instead of streamed. Not used when
+    /// tracing with [`verbosity`](Self::verbosity), which needs the parse.
+    pub fn cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.cache = dir.map(Cache::new);
+        self
+    }
+
+    /// Only compare values at or below `path`, such as `["ingress"]`, leaving
+    /// the rest of every file out. Files still count as redundant or empty
+    /// by all of their values.
+    pub fn root(mut self, path: &[String]) -> Self {
+        self.root = path.to_vec();
+        self
+    }
+
+    /// Show the paths of findings relative to [`root`](Self::root), so
+    /// `ingress.tls` is just `tls`.
+    pub fn relative_to_root(mut self, relative: bool) -> Self {
+        self.relative_to_root = relative;
+        self
+    }
+
+    /// Compare documents that are a single scalar or list rather than a
+    /// mapping, such as a file holding just `hello`. They are left out by
+    /// default: such a document replaces the whole value instead of
+    /// overriding keys, and all of them share the same empty path.
+    pub fn include_root_scalars(mut self, include: bool) -> Self {
+        self.include_root_scalars = include;
+        self
+    }
+
+    /// Parse layers as `format` when [`InputFormat::detect`] can't tell from
+    /// their name, such as stdin. Defaults to YAML.
+    pub fn input_format(mut self, format: Option<InputFormat>) -> Self {
+        self.input_format = format;
+        self
+    }
+
+    /// Leave out files that can't be read, reporting each as a
+    /// [`Finding::SkippedFile`], instead of failing the whole analysis.
+    /// Files that are read but don't parse still fail it.
+    pub fn skip_missing(mut self, skip_missing: bool) -> Self {
+        self.skip_missing = skip_missing;
+        self
+    }
+
+    /// Time limit for fetching each `http(s)://` layer; `None` waits as
+    /// long as the server keeps the connection open.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Trace parsing to stderr: 1 logs each parser event with the path and
+    /// state it leaves the collector in, 2 also logs every value recorded.
+    pub fn verbosity(mut self, verbosity: u8) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Count the layers parsed so far on stderr while parsing, for runs over
+    /// many files. Only meant for a terminal: the line is redrawn in place.
+    pub fn progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Make each `---` document read from stdin a layer of its own, in
+    /// document order, labelled `<stdin>#0`, `<stdin>#1` and so on, for
+    /// overrides generated into a single stream. Lines still count from the
+    /// start of stdin. Stdin is then read whole rather than streamed.
+    pub fn stdin_multidoc(mut self, multidoc: bool) -> Self {
+        self.stdin_multidoc = multidoc;
+        self
+    }
+
+    /// Cap the number of threads used to parse files; `None` uses all cores.
+    pub fn jobs(mut self, jobs: Option<usize>) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// The values `b` restates from `a` when applied over it, which is all of
+    /// them if `b` is redundant. Only the two files are compared; keys
+    /// repeated within either file aren't looked at.
+    pub fn compare(a: &Path, b: &Path) -> Result<Vec<Override>> {
+        let (pointless, _) = Self::new(a.to_path_buf(), vec![b.to_path_buf()])
+            .only(&[Category::Overrides])
+            .analyze()?;
+        Ok(pointless)
+    }
+
+    pub fn analyze(&self) -> Result<(Vec<Override>, Vec<DuplicateKeyWarning>)> {
+        Ok(split_findings(self.findings()?))
+    }
+
+    /// All findings, ordered by file (in layer order) and then by line.
+    ///
+    /// Files are streamed through the parser a line at a time rather than
+    /// read whole; use [`read_sources`](Self::read_sources) and
+    /// [`findings_in`](Self::findings_in) when the text is needed afterwards.
+    pub fn findings(&self) -> Result<impl Iterator<Item = Finding> + use<>> {
+        let (all_values, labels, skipped, blank) = self.parsed_layers()?;
+        Ok(self.findings_from(&all_values, &labels, skipped, &blank))
+    }
+
+    /// Every value of every layer, in priority order, as the analysis sees
+    /// it before comparing anything. With [`skip_missing`](Self::skip_missing)
+    /// the files that can't be read are left out.
+    pub fn collect(&self) -> Result<Vec<FileValues>> {
+        let (all_values, labels, _, _) = self.parsed_layers()?;
+        Ok(labels
+            .into_iter()
+            .zip(all_values)
+            .map(|(file, values)| FileValues {
+                file,
+                values: values
+                    .into_iter()
+                    .map(|(path, value)| (path, value.value, value.line))
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// The values in effect once every layer is applied, as YAML, with a
+    /// `# from file:line` comment after each value naming the layer that set
+    /// it. Layers merge as Helm merges values files: mappings key by key,
+    /// while a list, like a scalar, replaces the earlier one whole rather
+    /// than being appended to. A null above the lowest layer removes its
+    /// key, and `{}` leaves a mapping as it was.
+    pub fn merged(&self) -> Result<String> {
+        let (all_values, _, _, _) = self.parsed_layers()?;
+        Ok(merge::merged_yaml(&precedence_order(
+            &all_values,
+            self.options,
+        )))
+    }
+
+    /// The values of each layer that could be read, their labels, the layers
+    /// skipped because they couldn't, and the labels of blank layers.
+    fn parsed_layers(&self) -> Result<ParsedLayers> {
+        if let Some(sources) = &self.sources {
+            let (mut all_values, mut labels) = self.values_and_labels(sources)?;
+            self.push_set_layer(&mut all_values, &mut labels);
+            return Ok((all_values, labels, Vec::new(), blank_sources(sources)));
+        }
+
+        let files = self.layers()?;
+        if self.stdin_multidoc && files.iter().any(|path| is_stdin(path)) {
+            // How many layers stdin makes is only known once it is parsed
+            let (sources, skipped) = self.read_available_sources()?;
+            let (mut all_values, mut labels) = self.values_and_labels(&sources)?;
+            self.push_set_layer(&mut all_values, &mut labels);
+            return Ok((all_values, labels, skipped, blank_sources(&sources)));
+        }
+        let progress = Progress::new(self.progress, files.len());
+        // Files stream through the parser, so reading is part of parsing
+        let results: Vec<Result<(Entries, bool)>> = self.timings.phase("read and parse", || {
+            self.run_parallel(|| {
+                Ok(files
+                    .par_iter()
+                    .map(|path| {
+                        self.timings
+                            .layer(source_label(path), || match self.active_cache() {
+                                Some(cache) => SourceFile::read_with_timeout(path, self.timeout)
+                                    .and_then(|source| {
+                                        let values = self.cached_values(cache, &source)?;
+                                        Ok((values, is_blank(&source.content)))
+                                    }),
+                                None => {
+                                    let format = self.format_of(&source_label(path));
+                                    stream_values(path, self.timeout, format, self.verbosity)
+                                }
+                            })
+                    })
+                    .inspect(|_| progress.tick())
+                    .collect())
+            })
+        })?;
+        progress.finish();
+        let results = if results
+            .iter()
+            .any(|result| result.as_ref().is_err_and(is_parse_error))
+        {
+            self.reparse_sharing_anchors(&files, results)
+        } else {
+            results
+        };
+        let mut all_values = Vec::new();
+        let mut labels = Vec::new();
+        let mut skipped = Vec::new();
+        let mut blank = HashSet::new();
+        for (path, result) in files.iter().zip(results) {
+            match self.skip_unreadable(path, result)? {
+                Ok((values, is_blank)) => {
+                    all_values.push(values);
+                    labels.push(source_label(path));
+                    if is_blank {
+                        blank.insert(source_label(path));
+                    }
+                }
+                Err(skipped_file) => skipped.push(skipped_file),
+            }
+        }
+        self.push_set_layer(&mut all_values, &mut labels);
+        Ok((all_values, labels, skipped, blank))
+    }
+
+    /// Parse the layers again one after another from the lowest, so that
+    /// aliases can refer to the anchors of lower layers. Stdin can't be read
+    /// twice, so its first result stands, and so do other errors.
+    fn reparse_sharing_anchors(
+        &self,
+        files: &[&PathBuf],
+        results: Vec<Result<(Entries, bool)>>,
+    ) -> Vec<Result<(Entries, bool)>> {
+        let mut anchors = HashMap::new();
+        files
+            .iter()
+            .zip(results)
+            .map(|(path, result)| {
+                let format = self.format_of(&source_label(path));
+                match result {
+                    Err(error) if !is_parse_error(&error) => Err(error),
+                    result if format == InputFormat::Dotenv || is_stdin(path) => result,
+                    _ => {
+                        let source = SourceFile::read_with_timeout(path, self.timeout)?;
+                        let values =
+                            collect_sharing_anchors(&source, &mut anchors, self.verbosity)?;
+                        Ok((values, is_blank(&source.content)))
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Put the `--set` layer, if there is one, above the files.
+    fn push_set_layer(&self, all_values: &mut Vec<Entries>, labels: &mut Vec<String>) {
+        if !self.set_values.is_empty() {
+            all_values.push(self.set_values.clone());
+            labels.push(SET_LABEL.to_string());
+        }
+    }
+
+    /// With `skip_missing`, turn a read error into a [`SkippedFile`]; any
+    /// other error is passed on.
+    fn skip_unreadable<T>(&self, path: &Path, result: Result<T>) -> Result<Result<T, SkippedFile>> {
+        match result {
+            Ok(value) => Ok(Ok(value)),
+            Err(AnalyzeError::Io { source, .. }) if self.skip_missing => Ok(Err(SkippedFile {
+                file: source_label(path),
+                error: source.to_string(),
+            })),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Labels of every layer, in priority order, as findings refer to them.
+    /// Under [`stdin_multidoc`](Self::stdin_multidoc) stdin is read for its
+    /// documents, if the analysis hasn't read it already.
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels: Vec<String> = match &self.sources {
+            Some(sources) => sources.iter().map(|source| source.label.clone()).collect(),
+            None => self.layer_paths().map(|path| source_label(path)).collect(),
+        };
+        if let Some(index) = labels.iter().position(|label| label == STDIN_LABEL)
+            && let Some(documents) = self.stdin_documents()
+        {
+            labels.splice(index..=index, documents);
+        }
+        if !self.set_values.is_empty() {
+            labels.push(SET_LABEL.to_string());
+        }
+        labels
+    }
+
+    /// Labels of the documents stdin is split into under
+    /// [`stdin_multidoc`](Self::stdin_multidoc), reading it if that hasn't
+    /// happened yet. `None` when it isn't split, or can't be read or parsed,
+    /// which the analysis reports.
+    fn stdin_documents(&self) -> Option<Vec<String>> {
+        if !self.stdin_multidoc || self.format_of(STDIN_LABEL) == InputFormat::Dotenv {
+            return None;
+        }
+        let source = match &self.sources {
+            Some(sources) => sources
+                .iter()
+                .find(|source| source.label == STDIN_LABEL)?
+                .clone(),
+            None => self.read_layer(Path::new("-")).ok()?,
+        };
+        let documents = collect_documents(&source, 0).ok()?;
+        Some(documents.into_iter().map(|(label, _)| label).collect())
+    }
+
+    /// Read one layer. Under [`stdin_multidoc`](Self::stdin_multidoc) stdin
+    /// is only read the first time and its text kept.
+    fn read_layer(&self, path: &Path) -> Result<SourceFile> {
+        if !(self.stdin_multidoc && is_stdin(path)) {
+            return SourceFile::read_with_timeout(path, self.timeout);
+        }
+        if let Some(content) = self.stdin.get() {
+            return Ok(SourceFile {
+                label: STDIN_LABEL.to_string(),
+                content: content.clone(),
+            });
+        }
+        let source = SourceFile::read_with_timeout(path, self.timeout)?;
+        Ok(SourceFile {
+            content: self.stdin.get_or_init(|| source.content).clone(),
+            label: source.label,
+        })
+    }
+
+    /// Read every layer, from lowest to highest priority: defaults, base,
+    /// overrides. In-memory layers are returned as given.
+    pub fn read_sources(&self) -> Result<Vec<SourceFile>> {
+        if let Some(sources) = &self.sources {
+            return Ok(sources.clone());
+        }
+
+        let files = self.layers()?;
+        self.timings.phase("read", || {
+            self.run_parallel(|| files.par_iter().map(|path| self.read_layer(path)).collect())
+        })
+    }
+
+    /// Like [`read_sources`](Self::read_sources), but with
+    /// [`skip_missing`](Self::skip_missing) the files that can't be read are
+    /// returned separately instead of failing.
+    pub fn read_available_sources(&self) -> Result<(Vec<SourceFile>, Vec<SkippedFile>)> {
+        if let Some(sources) = &self.sources {
+            return Ok((sources.clone(), Vec::new()));
+        }
+
+        let files = self.layers()?;
+        let results: Vec<Result<SourceFile>> = self.timings.phase("read", || {
+            self.run_parallel(|| Ok(files.par_iter().map(|path| self.read_layer(path)).collect()))
+        })?;
+        let mut sources = Vec::new();
+        let mut skipped = Vec::new();
+        for (path, result) in files.iter().zip(results) {
+            match self.skip_unreadable(path, result)? {
+                Ok(source) => sources.push(source),
+                Err(skipped_file) => skipped.push(skipped_file),
+            }
+        }
+        Ok((sources, skipped))
+    }
+
+    /// Analyze layers that have already been read, in priority order.
+    pub fn analyze_sources(
+        &self,
+        sources: &[SourceFile],
+    ) -> Result<(Vec<Override>, Vec<DuplicateKeyWarning>)> {
+        Ok(split_findings(self.findings_in(sources)?))
+    }
+
+    /// Findings for layers that have already been read, ordered like
+    /// [`findings`](Self::findings).
+    pub fn findings_in(
+        &self,
+        sources: &[SourceFile],
+    ) -> Result<impl Iterator<Item = Finding> + use<>> {
+        let (mut all_values, mut labels) = self.values_and_labels(sources)?;
+        self.push_set_layer(&mut all_values, &mut labels);
+        Ok(self.findings_from(&all_values, &labels, Vec::new(), &blank_sources(sources)))
+    }
+
+    /// The values of each source with its label. Under
+    /// [`stdin_multidoc`](Self::stdin_multidoc) stdin's entry is replaced by
+    /// one for each of its documents.
+    fn values_and_labels(&self, sources: &[SourceFile]) -> Result<(Vec<Entries>, Vec<String>)> {
+        let mut all_values = Vec::new();
+        let mut labels = Vec::new();
+        for (source, values) in sources.iter().zip(self.values_in(sources)?) {
+            if self.stdin_multidoc
+                && source.label == STDIN_LABEL
+                && self.format_of(&source.label) != InputFormat::Dotenv
+            {
+                let documents = collect_documents(source, self.verbosity)?;
+                for (label, values) in documents {
+                    labels.push(label);
+                    all_values.push(values);
+                }
+            } else {
+                labels.push(source.label.clone());
+                all_values.push(values);
+            }
+        }
+        Ok((all_values, labels))
+    }
+
+    fn values_in(&self, sources: &[SourceFile]) -> Result<Vec<Entries>> {
+        // Files parse independently, so do that in parallel; collecting keeps
+        // the layer order the comparison below depends on
+        let progress = Progress::new(self.progress, sources.len());
+        let values = self.timings.phase("parse", || {
+            self.run_parallel(|| {
+                sources
+                    .par_iter()
+                    .map(|source| {
+                        self.timings
+                            .layer(source.label.clone(), || match self.active_cache() {
+                                Some(cache) => self.cached_values(cache, source),
+                                None => collect_values(
+                                    source,
+                                    self.format_of(&source.label),
+                                    self.verbosity,
+                                ),
+                            })
+                    })
+                    .inspect(|_| progress.tick())
+                    .collect()
+            })
+        });
+        progress.finish();
+        match values {
+            Err(error) if is_parse_error(&error) => self.values_sharing_anchors(sources),
+            values => values,
+        }
+    }
+
+    /// The values of each layer, parsed one after another from the lowest so
+    /// that aliases can refer to the anchors of lower layers.
+    fn values_sharing_anchors(&self, sources: &[SourceFile]) -> Result<Vec<Entries>> {
+        let mut anchors = HashMap::new();
+        sources
+            .iter()
+            .map(|source| match self.format_of(&source.label) {
+                InputFormat::Dotenv => collect_values(source, InputFormat::Dotenv, self.verbosity),
+                _ => collect_sharing_anchors(source, &mut anchors, self.verbosity),
+            })
+            .collect()
+    }
+
+    fn active_cache(&self) -> Option<&Cache> {
+        self.cache.as_ref().filter(|_| self.verbosity == 0)
+    }
+
+    fn cached_values(&self, cache: &Cache, source: &SourceFile) -> Result<Entries> {
+        let format = self.format_of(&source.label);
+        if let Some(values) = cache.load(source, format) {
+            return Ok(values);
+        }
+        let values = collect_values(source, format, self.verbosity)?;
+        cache.store(source, format, &values);
+        Ok(values)
+    }
+
+    fn format_of(&self, label: &str) -> InputFormat {
+        InputFormat::detect(label)
+            .or(self.input_format)
+            .unwrap_or(InputFormat::Yaml)
+    }
+
+    fn findings_from(
+        &self,
+        all_values: &[Vec<(Vec<String>, ValueWithLocation)>],
+        labels: &[String],
+        skipped: Vec<SkippedFile>,
+        blank: &HashSet<String>,
+    ) -> impl Iterator<Item = Finding> + use<> {
+        let drop_root = !self.include_root_scalars
+            && all_values.iter().flatten().any(|(path, _)| path.is_empty());
+        let prepared;
+        let all_values = if drop_root || !self.list_merge_keys.is_empty() {
+            prepared = all_values
+                .iter()
+                .map(|values| {
+                    let values = values
+                        .iter()
+                        .filter(|(path, _)| !(drop_root && path.is_empty()))
+                        .cloned()
+                        .collect();
+                    key_list_elements(values, &self.list_merge_keys)
+                })
+                .collect::<Vec<Entries>>();
+            prepared.as_slice()
+        } else {
+            all_values
+        };
+        let mut keys: Vec<&dyn KeyNormalizer> = Vec::new();
+        if self.options.case_insensitive_keys {
+            keys.push(&LowercaseKeys);
+        }
+        keys.extend(self.key_normalizer.as_deref());
+        let keys = Chain::new(keys);
+        // Redundant and empty files go by everything a file sets, the rest
+        // only by what is below the root
+        let whole_files = all_values;
+        let scoped;
+        let all_values = if self.root.is_empty() {
+            all_values
+        } else {
+            let root = path_key(&self.root, &keys);
+            scoped = all_values
+                .iter()
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter(|(path, _)| path_key(path, &keys).starts_with(&root))
+                        .cloned()
+                        .collect()
+                })
+                .collect::<Vec<Entries>>();
+            scoped.as_slice()
+        };
+        let expand_env = ExpandEnv::default();
+        let mut values: Vec<&dyn ValueNormalizer> = Vec::new();
+        if self.options.expand_env {
+            values.push(&expand_env);
+        }
+        let equivalences = Equivalences(&self.equivalences);
+        if !self.equivalences.is_empty() {
+            values.push(&equivalences);
+        }
+        values.extend(self.value_normalizer.as_deref());
+        // Comparing by quantity or YAML type is a normalization, so strict
+        // comparison leaves them out
+        let quantities = Quantities(&self.quantity_paths);
+        if !self.options.strict_strings {
+            if !self.quantity_paths.is_empty() {
+                values.push(&quantities);
+            }
+            values.push(&YamlScalars);
+        }
+        let values = Chain::new(values);
+        let values = (!values.is_empty()).then_some(&values as &dyn ValueNormalizer);
+
+        let dup_checked: Vec<bool> = labels
+            .iter()
+            .map(|label| {
+                let label = label.strip_prefix("./").unwrap_or(label);
+                !self
+                    .no_dup_check
+                    .iter()
+                    .any(|pattern| pattern.matches(label))
+            })
+            .collect();
+        let checks_started = Instant::now();
+        let (pointless, warnings, mut effective) = self.timings.phase("compare", || {
+            find_pointless_overrides_and_warnings(
+                all_values,
+                &dup_checked,
+                self.options,
+                &keys,
+                values,
+            )
+        });
+        let conflicts = if self.warn_conflicts {
+            let lower = self.lower_labels();
+            let overlays: Vec<Entries> = all_values
+                .iter()
+                .zip(labels)
+                .filter(|(_, label)| !lower.contains(label) && *label != SET_LABEL)
+                .map(|(values, _)| values.clone())
+                .collect();
+            find_conflicts(&overlays, self.options, &keys, values)
+        } else {
+            Vec::new()
+        };
+        let type_mismatches = if self.options.skip_types {
+            Vec::new()
+        } else {
+            find_type_mismatches(all_values, self.options, &keys, values)
+        };
+        let compared = checks_started.elapsed();
+        let unset = expand_env.unset.into_inner().unwrap_or_default();
+        for name in unset {
+            eprintln!(
+                "{} environment variable `{name}` is not set; leaving `${{{name}}}` as written",
+                "warning:".yellow().bold()
+            );
+        }
+        if !self.show_effective {
+            effective.clear();
+        }
+        let redundant = redundant_files(whole_files, &pointless, self.options);
+        let empty = empty_files(whole_files, labels, blank, self.options);
+        let stats = if self.stats {
+            file_stats(all_values, labels, &pointless, self.options)
+        } else {
+            Vec::new()
+        };
+        let subtrees = if self.rollup {
+            redundant_subtrees(all_values, &pointless, self.options)
+        } else {
+            Vec::new()
+        };
+        let provenance = if self.provenance {
+            path_provenance(all_values, self.options, &keys)
+        } else {
+            Vec::new()
+        };
+        let dead_keys = if self.dead_keys {
+            // The defaults sit below the base: their keys aren't the base's
+            // to drop, and they don't keep any base key in use
+            let defaults = self
+                .defaults_file
+                .as_ref()
+                .filter(|_| self.sources.is_none())
+                .map(|path| source_label(path));
+            let layers: Vec<Entries> = all_values
+                .iter()
+                .zip(labels)
+                .filter(|(_, label)| defaults.as_ref() != Some(*label))
+                .map(|(values, _)| values.clone())
+                .collect();
+            find_dead_keys(&layers, self.options, &keys)
+        } else {
+            Vec::new()
+        };
+        let null_empty = if self.warn_null_empty {
+            find_null_empty(all_values, self.options, &keys)
+        } else {
+            Vec::new()
+        };
+
+        let mut findings: Vec<Finding> = pointless
+            .into_iter()
+            .map(Finding::PointlessOverride)
+            .chain(warnings.into_iter().map(Finding::DuplicateKey))
+            .chain(effective.into_iter().map(Finding::EffectiveOverride))
+            .chain(type_mismatches.into_iter().map(Finding::TypeMismatch))
+            .chain(null_empty.into_iter().map(Finding::NullEmpty))
+            .chain(conflicts.into_iter().map(Finding::Conflict))
+            .chain(redundant.into_iter().map(Finding::RedundantFile))
+            .chain(subtrees.into_iter().map(Finding::RedundantSubtree))
+            .chain(empty.into_iter().map(Finding::EmptyFile))
+            .chain(stats.into_iter().map(Finding::FileStats))
+            .chain(provenance.into_iter().map(Finding::Provenance))
+            .chain(dead_keys.into_iter().map(Finding::DeadKey))
+            .chain(
+                self.max_depth
+                    .into_iter()
+                    .flat_map(|max_depth| find_deep_paths(all_values, max_depth))
+                    .map(Finding::DeepPath),
+            )
+            .chain(skipped.into_iter().map(Finding::SkippedFile))
+            .collect();
+        if self.relative_to_root {
+            for finding in &mut findings {
+                if let Some(path) = finding.path_mut() {
+                    path.drain(..self.root.len().min(path.len()));
+                }
+            }
+        }
+        // Findings about whole files have no path for a pattern to match,
+        // not even `*`. Only findings that point out a problem count as
+        // suppressed, not the rows describing the files
+        let mut suppressed = 0;
+        findings.retain(|finding| {
+            if finding.path().is_empty() || !self.ignore.is_ignored(finding.path()) {
+                return true;
+            }
+            if finding.is_problem() {
+                suppressed += 1;
+            }
+            false
+        });
+        *self
+            .suppressed
+            .lock()
+            .unwrap_or_else(|error| error.into_inner()) = suppressed;
+        let layer = |file: &str| labels.iter().position(|label| label == file);
+        findings.sort_by_key(|finding| (layer(finding.file()), finding.line(), finding.column()));
+        self.timings
+            .record("other checks", checks_started.elapsed() - compared);
+        self.timings.report();
+        findings.into_iter()
+    }
+
+    /// Labels of the layers below the override files: the base and, when
+    /// files are read, the defaults.
+    fn lower_labels(&self) -> Vec<String> {
+        match &self.sources {
+            Some(sources) => sources
+                .first()
+                .map(|source| source.label.clone())
+                .into_iter()
+                .collect(),
+            None => self
+                .defaults_file
+                .iter()
+                .chain(std::iter::once(&self.base_file))
+                .map(|path| source_label(path))
+                .collect(),
+        }
+    }
+
+    fn layer_paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.defaults_file
+            .iter()
+            .chain(std::iter::once(&self.base_file))
+            .chain(&self.override_files)
+    }
+
+    /// Every layer's path, checking that stdin is read at most once.
+    fn layers(&self) -> Result<Vec<&PathBuf>> {
+        let files: Vec<&PathBuf> = self.layer_paths().collect();
+        if files.iter().filter(|path| is_stdin(path)).count() > 1 {
+            return Err(AnalyzeError::StdinReused);
+        }
+        Ok(files)
+    }
+
+    fn run_parallel<T: Send>(&self, work: impl FnOnce() -> Result<T> + Send) -> Result<T> {
+        match self.jobs {
+            Some(jobs) => ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build()?
+                .install(work),
+            None => work(),
+        }
+    }
+}
+
+/// The values one layer sets, as returned by [`PointlessPointer::collect`].
+#[derive(Debug, Clone, Serialize)]
+pub struct FileValues {
+    pub file: String,
+    /// Path, value as written and line of every scalar, in document order.
+    /// A list of scalars is a single value, rendered in flow style
+    /// (`[a, b]`); a document that is just a scalar has the empty path.
+    pub values: Vec<(Vec<String>, String, usize)>,
+}
+
+/// Label of the layer made of [`PointlessPointer::set_values`], the way
+/// findings refer to it.
+pub const SET_LABEL: &str = "--set";
+
+/// Label used in place of a file name for content read from stdin.
+pub const STDIN_LABEL: &str = "<stdin>";
+
+/// The raw contents of one layer, labelled the way findings refer to it.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub label: String,
+    pub content: String,
+}
+
+impl SourceFile {
+    /// Read a file, stdin when the path is `-`, or an `http(s)://` URL.
+    pub fn read(path: &Path) -> Result<Self> {
+        Self::read_with_timeout(path, None)
+    }
+
+    /// Like [`read`](Self::read), with a time limit for fetching URLs.
+    pub fn read_with_timeout(path: &Path, timeout: Option<Duration>) -> Result<Self> {
+        let content = if is_stdin(path) {
+            let mut content = String::new();
+            io::stdin()
+                .read_to_string(&mut content)
+                .map_err(AnalyzeError::io(path))?;
+            content
+        } else if is_url(path) {
+            let mut content = String::new();
+            remote::open(&path.to_string_lossy(), timeout)?
+                .read_to_string(&mut content)
+                .map_err(AnalyzeError::io(path))?;
+            content
+        } else {
+            fs::read_to_string(path).map_err(AnalyzeError::io(path))?
+        };
+        Ok(Self {
+            label: source_label(path),
+            content,
+        })
+    }
+
+    /// Numbered lines within `context` lines of `line` (both 1-based).
+    pub fn lines_around(&self, line: usize, context: usize) -> Vec<(usize, &str)> {
+        let first = line.saturating_sub(context).max(1);
+        self.content
+            .lines()
+            .enumerate()
+            .map(|(index, text)| (index + 1, text))
+            .skip(first - 1)
+            .take(line + context + 1 - first)
+            .collect()
+    }
+}
+
+/// Layers parsed so far out of all of them, redrawn in place on stderr as
+/// each one finishes. Does nothing unless enabled.
+struct Progress {
+    enabled: bool,
+    total: usize,
+    /// Held while drawing, so parallel updates come out in order
+    done: Mutex<usize>,
+}
+
+impl Progress {
+    fn new(enabled: bool, total: usize) -> Self {
+        Self {
+            enabled,
+            total,
+            done: Mutex::new(0),
+        }
+    }
+
+    fn tick(&self) {
+        if !self.enabled {
+            return;
+        }
+        let mut done = self.done.lock().unwrap_or_else(|error| error.into_inner());
+        *done += 1;
+        eprint!("\rParsing files: {}/{}", *done, self.total);
+    }
+
+    /// Clear the line for whatever is printed next.
+    fn finish(&self) {
+        if self.enabled {
+            eprint!("\r\x1B[K");
+        }
+    }
+}
+
+/// Wall time spent in each phase of an analysis and on each layer, printed
+/// to stderr once the analysis is done. Records nothing unless enabled.
+#[derive(Default)]
+struct Timings {
+    enabled: bool,
+    phases: Mutex<Vec<(&'static str, Duration)>>,
+    /// Parsing time of each layer, reading included when files stream
+    layers: Mutex<Vec<(String, Duration)>>,
+}
+
+impl Timings {
+    fn phase<T>(&self, name: &'static str, work: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = work();
+        self.record(name, started.elapsed());
+        result
+    }
+
+    fn layer<T>(&self, label: String, work: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return work();
+        }
+        let started = Instant::now();
+        let result = work();
+        let elapsed = started.elapsed();
+        self.layers
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push((label, elapsed));
+        result
+    }
+
+    fn record(&self, name: &'static str, elapsed: Duration) {
+        if self.enabled {
+            self.phases
+                .lock()
+                .unwrap_or_else(|error| error.into_inner())
+                .push((name, elapsed));
+        }
+    }
+
+    /// Print the phases in the order they ran, each layer under the phase
+    /// that parsed it, slowest first, and forget them for the next analysis.
+    fn report(&self) {
+        if !self.enabled {
+            return;
+        }
+        fn take<T>(list: &Mutex<Vec<T>>) -> Vec<T> {
+            std::mem::take(&mut *list.lock().unwrap_or_else(|error| error.into_inner()))
+        }
+        let phases = take(&self.phases);
+        let mut layers = take(&self.layers);
+        layers.sort_by_key(|(_, elapsed)| std::cmp::Reverse(*elapsed));
+        // Layers are indented two more than phases
+        let width = layers
+            .iter()
+            .map(|(label, _)| label.chars().count() + 2)
+            .chain(phases.iter().map(|(name, _)| name.len()))
+            .max()
+            .unwrap_or(0);
+        let inner = width - 2;
+        eprintln!("{}", "Timings:".bold());
+        for (name, elapsed) in &phases {
+            eprintln!("  {name:<width$}  {:>10}", format!("{elapsed:.1?}"));
+            if name.contains("parse") {
+                for (label, elapsed) in &layers {
+                    eprintln!("    {label:<inner$}  {:>10}", format!("{elapsed:.1?}"));
+                }
+            }
+        }
+        let total: Duration = phases.iter().map(|(_, elapsed)| *elapsed).sum();
+        eprintln!("  {:<width$}  {:>10}", "total", format!("{total:.1?}"));
+    }
+}
+
+/// How a layer's text is parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputFormat {
+    Yaml,
+    /// `KEY=VALUE` lines, with `__` separating the segments of a nested path:
+    /// `DB__HOST=db` sets `DB.HOST`
+    Dotenv,
+    /// Parsed as the YAML it is a subset of; only [`fix`] treats it apart
+    Json,
+}
+
+impl InputFormat {
+    /// The format a layer's name gives away: `*.yaml` and `*.yml` are YAML,
+    /// `*.json` is JSON, and `.env`, `.env.*` and `*.env` are
+    /// [`Dotenv`](Self::Dotenv).
+    /// `None` for any other name, stdin's included.
+    pub fn detect(label: &str) -> Option<Self> {
+        let name = label.rsplit(['/', '\\']).next().unwrap_or(label);
+        if name.ends_with(".yaml") || name.ends_with(".yml") {
+            Some(InputFormat::Yaml)
+        } else if name.ends_with(".json") {
+            Some(InputFormat::Json)
+        } else if name == ".env" || name.starts_with(".env.") || name.ends_with(".env") {
+            Some(InputFormat::Dotenv)
+        } else {
+            None
+        }
+    }
+}
+
+fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// Whether a layer is fetched over HTTP(S) rather than read from disk. The
+/// URL itself is then the layer's label.
+pub fn is_url(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+fn source_label(path: &Path) -> String {
+    if is_stdin(path) {
+        STDIN_LABEL.to_string()
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// Byte order mark some editors put at the start of UTF-8 files. It isn't
+/// part of the content, and left in it would become part of the first key.
+const BOM: char = '\u{feff}';
+
+fn without_bom(content: &str) -> &str {
+    content.strip_prefix(BOM).unwrap_or(content)
+}
+
+fn parse_source(source: &SourceFile, verbosity: u8) -> Result<YamlValueCollector> {
+    let mut collector = YamlValueCollector::new(source.label.clone(), verbosity);
+    let mut parser = Parser::new_from_str(without_bom(&source.content));
+    parser
+        .load(&mut collector, true)
+        .map_err(AnalyzeError::parse(&source.label))?;
+    Ok(collector)
+}
+
+/// Like [`collect_values`] for YAML, but aliases may refer to the anchors of
+/// lower layers in `anchors`, to which this file's anchors are then added.
+/// Aliases to anchors the file doesn't have are replaced by placeholders up
+/// front, so the file is parsed once; a second time only when something
+/// taken for an alias turns out to be inside a scalar.
+fn collect_sharing_anchors(
+    source: &SourceFile,
+    anchors: &mut HashMap<String, Collected>,
+    verbosity: u8,
+) -> Result<Entries> {
+    let original: Vec<char> = without_bom(&source.content).chars().collect();
+    let mut aliases = unknown_aliases(&original);
+    let mut collector = loop {
+        let mut text = original.clone();
+        for (start, name) in &aliases {
+            text[*start] = '~';
+            text[start + 1..start + 1 + name.chars().count()].fill(' ');
+        }
+        let mut collector = YamlValueCollector::new(source.label.clone(), verbosity);
+        collector.shared = Some(SharedAnchors {
+            placeholders: aliases.iter().cloned().collect(),
+            lower: std::mem::take(anchors),
+            ..SharedAnchors::default()
+        });
+        let content: String = text.iter().collect();
+        if let Some(shared) = &mut collector.shared {
+            shared.text = text;
+        }
+        let parsed = Parser::new_from_str(&content).load(&mut collector, true);
+        let Some(shared) = &mut collector.shared else {
+            unreachable!("the collector was given shared anchors");
+        };
+        *anchors = std::mem::take(&mut shared.lower);
+        parsed.map_err(AnalyzeError::parse(&source.label))?;
+        // Placeholders the collector didn't take as a node were never aliases
+        if shared.placeholders.is_empty() {
+            break collector;
+        }
+        aliases.retain(|(start, _)| !shared.placeholders.contains_key(start));
+    };
+    anchors.extend(collector.named_anchors());
+    if let Some(shared) = &collector.shared {
+        for (line, name) in &shared.unresolved {
+            eprintln!(
+                "{} {}:{line}: alias `*{name}` matches no anchor in this file or an earlier one; leaving it out",
+                "warning:".yellow().bold(),
+                source.label
+            );
+        }
+    }
+
+    let suppressed = suppressed_lines(&source.content);
+    let mut values = collector.values;
+    for (_, value) in &mut values {
+        value.suppressed = suppressed.contains(&value.line) || suppressed.contains(&value.key_line);
+    }
+    Ok(values)
+}
+
+/// Receives the events of a stream with a new collector for each document,
+/// so that each document becomes a layer of its own.
+struct DocumentCollectors {
+    label: String,
+    verbosity: u8,
+    collectors: Vec<YamlValueCollector>,
+}
+
+impl<'input> SpannedEventReceiver<'input> for DocumentCollectors {
+    fn on_event(&mut self, event: Event<'input>, span: Span) {
+        if let Event::DocumentStart(_) = event {
+            let label = format!("{}#{}", self.label, self.collectors.len());
+            self.collectors
+                .push(YamlValueCollector::new(label, self.verbosity));
+        }
+        if let Some(collector) = self.collectors.last_mut() {
+            collector.on_event(event, span);
+        }
+    }
+}
+
+/// The values of each `---` document of `source`, labelled `label#0`,
+/// `label#1` and so on.
+fn collect_documents(source: &SourceFile, verbosity: u8) -> Result<Vec<(String, Entries)>> {
+    let mut documents = DocumentCollectors {
+        label: source.label.clone(),
+        verbosity,
+        collectors: Vec::new(),
+    };
+    Parser::new_from_str(without_bom(&source.content))
+        .load(&mut documents, true)
+        .map_err(AnalyzeError::parse(&source.label))?;
+    let suppressed = suppressed_lines(&source.content);
+    Ok(documents
+        .collectors
+        .into_iter()
+        .map(|collector| {
+            let mut values = collector.values;
+            for (_, value) in &mut values {
+                value.suppressed =
+                    suppressed.contains(&value.line) || suppressed.contains(&value.key_line);
+            }
+            (collector.current_file, values)
+        })
+        .collect())
+}
+
+/// Whether a layer isn't valid on its own, which may be an alias to an
+/// anchor of a lower layer.
+fn is_parse_error(error: &AnalyzeError) -> bool {
+    matches!(error, AnalyzeError::Parse { .. })
+}
+
+fn collect_values(
+    source: &SourceFile,
+    format: InputFormat,
+    verbosity: u8,
+) -> Result<Vec<(Vec<String>, ValueWithLocation)>> {
+    let suppressed = suppressed_lines(&source.content);
+    let mut values = match format {
+        InputFormat::Yaml | InputFormat::Json => parse_source(source, verbosity)?.values,
+        InputFormat::Dotenv => dotenv::parse(&source.label, without_bom(&source.content))
+            .map_err(AnalyzeError::parse(&source.label))?,
+    };
+    for (_, value) in &mut values {
+        value.suppressed = suppressed.contains(&value.line) || suppressed.contains(&value.key_line);
+    }
+    Ok(values)
+}
+
+/// Parse a file (or stdin) straight from a buffered reader, without holding
+/// its whole text in memory. Only YAML and JSON stream; dotenv files are read
+/// whole.
+/// Also says whether the input is blank.
+fn stream_values(
+    path: &Path,
+    timeout: Option<Duration>,
+    format: InputFormat,
+    verbosity: u8,
+) -> Result<(Entries, bool)> {
+    let label = source_label(path);
+    if format == InputFormat::Dotenv {
+        let source = SourceFile::read_with_timeout(path, timeout)?;
+        let values = collect_values(&source, format, verbosity)?;
+        Ok((values, is_blank(&source.content)))
+    } else if is_stdin(path) {
+        collect_streamed(label, io::stdin().lock(), verbosity)
+    } else if is_url(path) {
+        let body = remote::open(&label, timeout)?;
+        collect_streamed(label, BufReader::new(body), verbosity)
+    } else {
+        let file = File::open(path).map_err(AnalyzeError::io(path))?;
+        collect_streamed(label, BufReader::new(file), verbosity)
+    }
+}
+
+fn collect_streamed(label: String, reader: impl BufRead, verbosity: u8) -> Result<(Entries, bool)> {
+    let mut chars = LineChars::new(reader);
+    let mut collector = YamlValueCollector::new(label.clone(), verbosity);
+    let parsed = Parser::new_from_iter(&mut chars).load(&mut collector, true);
+    // A read error ends the input early, which the parser may not notice
+    if let Some(error) = chars.error.take() {
+        return Err(AnalyzeError::io(&label)(error));
+    }
+    parsed.map_err(AnalyzeError::parse(&label))?;
+
+    let mut values = collector.values;
+    for (_, value) in &mut values {
+        value.suppressed =
+            chars.suppressed.contains(&value.line) || chars.suppressed.contains(&value.key_line);
+    }
+    Ok((values, chars.blank))
+}
+
+/// Whether `content` is nothing but whitespace.
+fn is_blank(content: &str) -> bool {
+    without_bom(content).trim().is_empty()
+}
+
+/// Labels of the sources that are blank.
+fn blank_sources(sources: &[SourceFile]) -> HashSet<String> {
+    sources
+        .iter()
+        .filter(|source| is_blank(&source.content))
+        .map(|source| source.label.clone())
+        .collect()
+}
+
+/// The characters of a reader, one line buffered at a time. Suppression
+/// comments are noted as each line goes by, since the parser drops comments
+/// and a streamed input (stdin in particular) can't be read a second time.
+struct LineChars<R> {
+    reader: R,
+    line: String,
+    position: usize,
+    line_number: usize,
+    suppressed: HashSet<usize>,
+    /// No line so far has had anything but whitespace
+    blank: bool,
+    error: Option<io::Error>,
+}
+
+impl<R: BufRead> LineChars<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            position: 0,
+            line_number: 0,
+            suppressed: HashSet::new(),
+            blank: true,
+            error: None,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for LineChars<R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.position == self.line.len() {
+            self.line.clear();
+            self.position = 0;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    self.line_number += 1;
+                    self.suppressed
+                        .extend(suppression_target(&self.line, self.line_number));
+                    if self.line_number == 1 && self.line.starts_with(BOM) {
+                        self.position = BOM.len_utf8();
+                    }
+                    self.blank &= self.line[self.position..].trim().is_empty();
+                }
+                Err(error) => {
+                    self.error = Some(error);
+                    self.line.clear();
+                    return None;
+                }
+            }
+        }
+
+        let next = self.line[self.position..].chars().next()?;
+        self.position += next.len_utf8();
+        Some(next)
+    }
+}
+
+/// Comment that silences findings on its own line, or on the next line when
+/// the comment stands alone.
+const SUPPRESSION_MARKER: &str = "pointless-pointer: ignore";
+
+/// Lines (1-based) carrying a suppression comment. The parser doesn't report
+/// comments, so they are found by scanning the raw content.
+fn suppressed_lines(content: &str) -> HashSet<usize> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| suppression_target(line, index + 1))
+        .collect()
+}
+
+/// The line a suppression comment on `line` (numbered `line_number`) applies
+/// to, if it carries one.
+fn suppression_target(line: &str, line_number: usize) -> Option<usize> {
+    let (code, comment) = line.split_once('#')?;
+    if !comment.contains(SUPPRESSION_MARKER) {
+        return None;
+    }
+    if code.trim().is_empty() {
+        Some(line_number + 1)
+    } else {
+        Some(line_number)
+    }
+}
+
+fn same_value(a: &ValueWithLocation, b: &ValueWithLocation, options: CompareOptions) -> bool {
+    if options.ignore_list_order && same_items(a, b, options) {
+        return true;
+    }
+    if options.strict_strings
+        && let (Some(a_items), Some(b_items)) = (&a.items, &b.items)
+    {
+        lists_identical(a_items, b_items)
+    } else if options.strict_strings {
+        // The type tells quoted from plain scalars, so `"1.10"` isn't `1.10`
+        a.compared() == b.compared() && a.value_type == b.value_type
+    } else if let (Some(a_items), Some(b_items)) = (&a.items, &b.items) {
+        lists_equivalent(a_items, b_items)
+    } else if is_block(a.style) && is_block(b.style) {
+        // The chomping indicator (`|`, `|-`, `|+`) only changes trailing newlines
+        a.compared().trim_end_matches('\n') == b.compared().trim_end_matches('\n')
+    } else {
+        // A null never equals a string that merely spells null, like `""`
+        a.is_null == b.is_null
+            && (a.compared() == b.compared()
+                || (options.float_epsilon > 0.0
+                    && is_number(a)
+                    && is_number(b)
+                    && normalize::within(a.compared(), b.compared(), options.float_epsilon)))
+    }
+}
+
+fn is_number(value: &ValueWithLocation) -> bool {
+    matches!(value.value_type, ValueType::Int | ValueType::Float)
+}
+
+/// Copies of the values prepared for comparison, with scalars rewritten by
+/// the value normalizers. Findings still show the values as written.
+fn prepare_values(
+    all_values: &[Vec<(Vec<String>, ValueWithLocation)>],
+    normalizer: &dyn ValueNormalizer,
+) -> Vec<Vec<(Vec<String>, ValueWithLocation)>> {
+    all_values
+        .iter()
+        .map(|values| {
+            values
+                .iter()
+                .cloned()
+                .map(|(path, mut value)| {
+                    if value.items.is_none() {
+                        let normalized = normalizer.normalize_value(&path, &value.value);
+                        if normalized != value.value.as_str() {
+                            value.normalized = Some(normalized.into_owned());
+                        }
+                    }
+                    (path, value)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The form of `path` that paths are matched on. Borrowed unless the
+/// normalizer changes a segment.
+fn path_key<'a>(path: &'a [String], keys: &dyn KeyNormalizer) -> Cow<'a, [String]> {
+    for (index, segment) in path.iter().enumerate() {
+        let first = keys.normalize_key(segment);
+        if first != segment.as_str() {
+            let mut owned = path[..index].to_vec();
+            owned.push(first.into_owned());
+            owned.extend(
+                path[index + 1..]
+                    .iter()
+                    .map(|segment| keys.normalize_key(segment).into_owned()),
+            );
+            return Cow::Owned(owned);
+        }
+    }
+    Cow::Borrowed(path)
+}
+
+/// Literal (`|`) and folded (`>`) block scalars. Their location is the first
+/// line of content, not the line with the indicator.
+fn is_block(style: ScalarStyle) -> bool {
+    matches!(style, ScalarStyle::Literal | ScalarStyle::Folded)
+}
+
+/// Whether two lists hold the same items in any order.
+fn same_items(a: &ValueWithLocation, b: &ValueWithLocation, options: CompareOptions) -> bool {
+    let (Some(a_items), Some(b_items)) = (&a.items, &b.items) else {
+        return false;
+    };
+    if a_items.len() != b_items.len() {
+        return false;
+    }
+    let matches = |x: &ListItem, y: &ListItem| {
+        if options.strict_strings {
+            x.identical(y)
+        } else {
+            x.equivalent(y)
+        }
+    };
+    // Items only have an equivalence, not a hash, so pair them up greedily
+    let mut unmatched: Vec<&ListItem> = b_items.iter().collect();
+    a_items.iter().all(
+        |item| match unmatched.iter().position(|other| matches(item, other)) {
+            Some(index) => {
+                unmatched.swap_remove(index);
+                true
+            }
+            None => false,
+        },
+    )
+}
+
+/// `keys` decides which paths are the same and `values`, when given,
+/// rewrites scalars before they are compared.
+/// `dup_checked` says, for each layer, whether to look for keys repeated
+/// within it.
+fn find_pointless_overrides_and_warnings(
+    all_values: &[Vec<(Vec<String>, ValueWithLocation)>],
+    dup_checked: &[bool],
+    options: CompareOptions,
+    keys: &dyn KeyNormalizer,
+    values: Option<&dyn ValueNormalizer>,
+) -> (
+    Vec<Override>,
+    Vec<DuplicateKeyWarning>,
+    Vec<EffectiveOverride>,
+) {
+    let prepared;
+    let all_values = match values {
+        Some(normalizer) => {
+            prepared = prepare_values(all_values, normalizer);
+            prepared.as_slice()
+        }
+        None => all_values,
+    };
+
+    let mut pointless = Vec::new();
+    let mut warnings = Vec::new();
+    let mut effective = Vec::new();
+
+    // Check for duplicates within each file first
+    if !(options.skip_duplicates && options.skip_warnings) {
+        for (values, _) in all_values
+            .iter()
+            .zip(dup_checked)
+            .filter(|(_, checked)| **checked)
+        {
+            let mut seen_in_file: HashMap<(usize, Cow<[String]>), &ValueWithLocation> =
+                HashMap::new();
+
+            for (path, value_loc) in values {
+                let key = (value_loc.document, path_key(path, keys));
+                if let Some(previous_in_file) = seen_in_file.get(&key)
+                    && !value_loc.suppressed
+                {
+                    // Found a duplicate within the same file
+                    if same_value(value_loc, previous_in_file, options) {
+                        if !(options.skip_duplicates || (options.null_deletes && value_loc.is_null))
+                        {
+                            pointless.push(Override {
+                                severity: Severity::Info,
+                                file: value_loc.file.clone(),
+                                path: path.clone(),
+                                value: value_loc.value.clone(),
+                                line: value_loc.line,
+                                column: value_loc.column,
+                                end_line: value_loc.end_line,
+                                end_column: value_loc.end_column,
+                                previous_value: previous_in_file.value.clone(),
+                                previous_file: previous_in_file.file.clone(),
+                                previous_line: previous_in_file.line,
+                                chain: Vec::new(),
+                            });
+                        }
+                    } else if !options.skip_warnings {
+                        // Same key but different values - create a warning
+                        warnings.push(DuplicateKeyWarning {
+                            severity: Severity::Error,
+                            file: value_loc.file.clone(),
+                            path: path.clone(),
+                            first_value: previous_in_file.value.clone(),
+                            first_line: previous_in_file.line,
+                            second_value: value_loc.value.clone(),
+                            second_line: value_loc.line,
+                            column: value_loc.column,
+                            end_line: value_loc.end_line,
+                            end_column: value_loc.end_column,
+                            first_key_line: previous_in_file.key_line,
+                            first_key_column: previous_in_file.key_column,
+                            second_key_line: value_loc.key_line,
+                            second_key_column: value_loc.key_column,
+                            whitespace_only: normalize::equivalent(
+                                previous_in_file.compared().trim(),
+                                value_loc.compared().trim(),
+                            ),
+                        });
+                    }
+                }
+                seen_in_file.insert(key, value_loc);
+            }
+        }
+    }
+
+    let layers = precedence_order(all_values, options);
+
+    // Then check for overrides across files
+    if layers.len() >= 2 && !options.skip_overrides {
+        // For each override file (starting from the second)
+        for i in 1..layers.len() {
+            let current_values = layers[i];
+
+            // Build effective values up to the previous file, or from the
+            // previous file alone when comparing pairwise
+            // Using HashMap to get the last value for each path (in case of duplicates)
+            // Keyed by document index too, so `---` documents line up by position
+            let mut effective_values: HashMap<(usize, Cow<[String]>), &ValueWithLocation> =
+                HashMap::new();
+            // Last value per file for each path, when the chain is wanted
+            let mut history: HashMap<(usize, Cow<[String]>), Vec<&ValueWithLocation>> =
+                HashMap::new();
+            let first = if options.pairwise { i - 1 } else { 0 };
+            // Lists replace the ones below them rather than merge, so their
+            // elements are only pointless when the whole list is restated
+            let changed = changed_lists(&layers[first..i], current_values, options, keys);
+            for value in &layers[first..i] {
+                for (path, value_loc) in value.iter() {
+                    let key = (value_loc.document, path_key(path, keys));
+                    if options.track_chain {
+                        let writes = history.entry(key.clone()).or_default();
+                        if writes
+                            .last()
+                            .is_some_and(|last| last.file == value_loc.file)
+                        {
+                            writes.pop();
+                        }
+                        writes.push(value_loc);
+                    }
+                    effective_values.insert(key, value_loc);
+                }
+            }
+
+            // Check current file for pointless overrides
+            for (path, current_value) in current_values {
+                let Some(previous_value) =
+                    effective_values.get(&(current_value.document, path_key(path, keys)))
+                else {
+                    continue;
+                };
+                let chain: Vec<ChainLink> = history
+                    .get(&(current_value.document, path_key(path, keys)))
+                    .into_iter()
+                    .flatten()
+                    .map(|write| ChainLink {
+                        file: write.file.clone(),
+                        line: write.line,
+                        value: write.value.clone(),
+                        same_value: same_value(current_value, write, options),
+                    })
+                    .collect();
+                if !same_value(current_value, previous_value, options) {
+                    // A real change - keep it for the effective-override audit
+                    effective.push(EffectiveOverride {
+                        file: current_value.file.clone(),
+                        path: path.clone(),
+                        value: current_value.value.clone(),
+                        line: current_value.line,
+                        column: current_value.column,
+                        end_line: current_value.end_line,
+                        end_column: current_value.end_column,
+                        previous_value: previous_value.value.clone(),
+                        previous_file: previous_value.file.clone(),
+                        previous_line: previous_value.line,
+                        reorder_only: same_items(current_value, previous_value, options),
+                        chain,
+                    });
+                } else if !(current_value.suppressed
+                    || (options.null_deletes && current_value.is_null)
+                    || list_depths(path, current_value).any(|depth| {
+                        changed.contains(&(current_value.document, path_key(&path[..depth], keys)))
+                    }))
+                {
+                    pointless.push(Override {
+                        severity: Severity::Warning,
+                        file: current_value.file.clone(),
+                        path: path.clone(),
+                        value: current_value.value.clone(),
+                        line: current_value.line,
+                        column: current_value.column,
+                        end_line: current_value.end_line,
+                        end_column: current_value.end_column,
+                        previous_value: previous_value.value.clone(),
+                        previous_file: previous_value.file.clone(),
+                        previous_line: previous_value.line,
+                        chain,
+                    });
+                }
+            }
+        }
+    }
+
+    (dedupe_overrides(pointless, &layers), warnings, effective)
+}
+
+/// Depths along `path` where it steps into a list by position. Keyed list
+/// elements are matched by identity instead, so they merge like mappings.
+fn list_depths<'a>(
+    path: &'a [String],
+    value: &'a ValueWithLocation,
+) -> impl Iterator<Item = usize> + 'a {
+    (0..path.len()).filter(|&depth| {
+        value.parents.get(depth) == Some(&ValueType::Sequence)
+            && path[depth].parse::<usize>().is_ok()
+    })
+}
+
+/// Lists in `current` that aren't the same as the list they replace, taken
+/// from the last of the `earlier` layers that has one at that path. Lists
+/// are the same when they hold the same values at the same positions.
+fn changed_lists<'a>(
+    earlier: &[&Entries],
+    current: &'a Entries,
+    options: CompareOptions,
+    keys: &dyn KeyNormalizer,
+) -> HashSet<(usize, Cow<'a, [String]>)> {
+    type Elements<'v> = HashMap<Vec<String>, &'v ValueWithLocation>;
+    /// Each list's values by document and list path, keyed by their path
+    /// within the list
+    fn lists<'v>(
+        values: &'v Entries,
+        keys: &dyn KeyNormalizer,
+    ) -> HashMap<(usize, Cow<'v, [String]>), Elements<'v>> {
+        let mut lists: HashMap<_, Elements> = HashMap::new();
+        for (path, value) in values {
+            for depth in list_depths(path, value) {
+                let within = path_key(&path[depth..], keys).into_owned();
+                lists
+                    .entry((value.document, path_key(&path[..depth], keys)))
+                    .or_default()
+                    .insert(within, value);
+            }
+        }
+        lists
+    }
+
+    let current_lists = lists(current, keys);
+    if current_lists.is_empty() {
+        return HashSet::new();
+    }
+    let earlier: Vec<_> = earlier.iter().map(|values| lists(values, keys)).collect();
+    current_lists
+        .into_iter()
+        .filter(|(list, elements)| {
+            let Some(previous) = earlier.iter().rev().find_map(|lists| lists.get(list)) else {
+                return false;
+            };
+            elements.len() != previous.len()
+                || elements.iter().any(|(within, value)| {
+                    previous
+                        .get(within)
+                        .is_none_or(|previous| !same_value(value, previous, options))
+                })
+        })
+        .map(|(list, _)| list)
+        .collect()
+}
+
+/// Give the elements of keyed lists their identity in place of their index,
+/// as `field=value`. The key field's own entry is dropped: it names the
+/// element rather than setting anything, so it would always look pointless.
+fn key_list_elements(values: Entries, merge_keys: &[(Pattern, String)]) -> Entries {
+    if merge_keys.is_empty() {
+        return values;
+    }
+
+    // Key field and value of each keyed element, by document and element path
+    let mut identities: HashMap<(usize, &[String]), (&str, &str)> = HashMap::new();
+    for (path, value) in &values {
+        let [element @ .., field] = path.as_slice() else {
+            continue;
+        };
+        let [list @ .., _] = element else {
+            continue;
+        };
+        let in_list = value.parents.get(list.len()) == Some(&ValueType::Sequence);
+        let keyed = merge_keys
+            .iter()
+            .any(|(pattern, key)| key == field && pattern.matches(&list.join(".")));
+        if in_list && keyed && value.items.is_none() {
+            identities.insert((value.document, element), (field, &value.value));
+        }
+    }
+    if identities.is_empty() {
+        return values;
+    }
+
+    values
+        .iter()
+        .filter(|(path, value)| {
+            let [element @ .., field] = path.as_slice() else {
+                return true;
+            };
+            identities
+                .get(&(value.document, element))
+                .is_none_or(|(key, _)| key != field)
+        })
+        .map(|(path, value)| {
+            let mut keyed_path = path.clone();
+            for depth in 1..path.len() {
+                if let Some((key, identity)) = identities.get(&(value.document, &path[..depth])) {
+                    keyed_path[depth - 1] = format!("{key}={identity}");
+                }
+            }
+            (keyed_path, value.clone())
+        })
+        .collect()
+}
+
+/// Layers in precedence order, lowest first.
+fn precedence_order(all_values: &[Entries], options: CompareOptions) -> Vec<&Entries> {
+    if options.reverse_precedence {
+        all_values.iter().rev().collect()
+    } else {
+        all_values.iter().collect()
+    }
+}
+
+/// Paths that a layer gives a different type than the layers below it. Each
+/// value also fixes the type of the collections along its path, so a scalar
+/// replaced by a mapping is found through the mapping's first entry.
+fn find_type_mismatches(
+    all_values: &[Entries],
+    options: CompareOptions,
+    keys: &dyn KeyNormalizer,
+    values: Option<&dyn ValueNormalizer>,
+) -> Vec<TypeMismatchWarning> {
+    let prepared;
+    let all_values = match values {
+        Some(normalizer) => {
+            prepared = prepare_values(all_values, normalizer);
+            prepared.as_slice()
+        }
+        None => all_values,
+    };
+    let mut mismatches = Vec::new();
+    // Latest type of every node, with the value that set it and whether that
+    // value is the node itself rather than something inside it
+    let mut types: HashMap<_, (ValueType, &ValueWithLocation, bool)> = HashMap::new();
+
+    for (layer, values) in precedence_order(all_values, options)
+        .into_iter()
+        .enumerate()
+    {
+        // One warning per node, not per entry below it
+        let mut reported = HashSet::new();
+        for (path, value) in values.iter().filter(|_| layer > 0) {
+            if value.suppressed {
+                continue;
+            }
+            for (depth, value_type) in node_types(path, value) {
+                let key = (value.document, path_key(&path[..depth], keys));
+                let Some(&(previous_type, previous, previous_leaf)) = types.get(&key) else {
+                    continue;
+                };
+                let leaf = depth == path.len();
+                if previous_type.compatible(value_type)
+                    || (leaf && previous_leaf && same_value(value, previous, options))
+                    || reported.contains(&key)
+                {
+                    continue;
+                }
+                mismatches.push(TypeMismatchWarning {
+                    severity: Severity::Warning,
+                    file: value.file.clone(),
+                    path: path[..depth].to_vec(),
+                    line: value.line,
+                    column: value.column,
+                    value_type,
+                    value: leaf.then(|| value.value.clone()),
+                    previous_file: previous.file.clone(),
+                    previous_line: previous.line,
+                    previous_type,
+                    previous_value: previous_leaf.then(|| previous.value.clone()),
+                });
+                reported.insert(key);
+            }
+        }
+        for (path, value) in values {
+            for (depth, value_type) in node_types(path, value) {
+                types.insert(
+                    (value.document, path_key(&path[..depth], keys)),
+                    (value_type, value, depth == path.len()),
+                );
+            }
+        }
+    }
+    mismatches
+}
+
+/// The last writer of every path, in the order the paths first appear.
+fn path_provenance(
+    all_values: &[Entries],
+    options: CompareOptions,
+    keys: &dyn KeyNormalizer,
+) -> Vec<PathProvenance> {
+    let mut order = Vec::new();
+    let mut writes: HashMap<_, (&[String], &ValueWithLocation, usize)> = HashMap::new();
+    for values in precedence_order(all_values, options) {
+        for (path, value) in values.iter() {
+            let key = (value.document, path_key(path, keys));
+            match writes.get_mut(&key) {
+                Some(write) => *write = (path, value, write.2 + 1),
+                None => {
+                    order.push(key.clone());
+                    writes.insert(key, (path, value, 1));
+                }
+            }
+        }
+    }
+    order
+        .into_iter()
+        .map(|key| {
+            let (path, value, count) = writes[&key];
+            PathProvenance {
+                path: path.to_vec(),
+                file: value.file.clone(),
+                line: value.line,
+                writes: count,
+                overridden: count - 1,
+            }
+        })
+        .collect()
+}
+
+/// Values of the lowest layer whose path no layer above touches: none sets
+/// the path itself, a path under it, or a path above it that replaces it.
+fn find_dead_keys(
+    all_values: &[Entries],
+    options: CompareOptions,
+    keys: &dyn KeyNormalizer,
+) -> Vec<DeadKey> {
+    let layers = precedence_order(all_values, options);
+    let Some((lowest, overlays)) = layers.split_first() else {
+        return Vec::new();
+    };
+    if overlays.is_empty() {
+        return Vec::new();
+    }
+    let mut set = HashSet::new();
+    let mut above = HashSet::new();
+    for (path, value) in overlays.iter().flat_map(|values| values.iter()) {
+        let key = path_key(path, keys);
+        for depth in 0..key.len() {
+            above.insert((value.document, key[..depth].to_vec()));
+        }
+        set.insert((value.document, key.into_owned()));
+    }
+    lowest
+        .iter()
+        .filter(|(path, value)| {
+            let key = path_key(path, keys);
+            !value.suppressed
+                && !above.contains(&(value.document, key.to_vec()))
+                && !(0..=key.len())
+                    .any(|depth| set.contains(&(value.document, key[..depth].to_vec())))
+        })
+        .map(|(path, value)| DeadKey {
+            file: value.file.clone(),
+            path: path.clone(),
+            value: value.value.clone(),
+            line: value.line,
+            column: value.column,
+        })
+        .collect()
+}
+
+/// Scalars that are null where the value below them is an empty string, or
+/// the other way round.
+fn find_null_empty(
+    all_values: &[Entries],
+    options: CompareOptions,
+    keys: &dyn KeyNormalizer,
+) -> Vec<NullEmptyWarning> {
+    let is_empty_string = |value: &ValueWithLocation| {
+        !value.is_null && value.value_type == ValueType::String && value.value.is_empty()
+    };
+    let mut warnings = Vec::new();
+    let mut latest: HashMap<(usize, Cow<[String]>), &ValueWithLocation> = HashMap::new();
+    for values in precedence_order(all_values, options) {
+        for (path, value) in values.iter() {
+            let key = (value.document, path_key(path, keys));
+            if let Some(previous) = latest.get(&key)
+                && !value.suppressed
+                && ((value.is_null && is_empty_string(previous))
+                    || (is_empty_string(value) && previous.is_null))
+                && previous.file != value.file
+            {
+                warnings.push(NullEmptyWarning {
+                    severity: Severity::Warning,
+                    file: value.file.clone(),
+                    path: path.clone(),
+                    line: value.line,
+                    column: value.column,
+                    is_null: value.is_null,
+                    value: value.value.clone(),
+                    previous_file: previous.file.clone(),
+                    previous_line: previous.line,
+                    previous_value: previous.value.clone(),
+                });
+            }
+            latest.insert(key, value);
+        }
+    }
+    warnings
+}
+
+/// Values of the override files `overlays` that differ from what an earlier
+/// one of them sets at the same path, compared as pointless overrides are.
+fn find_conflicts(
+    overlays: &[Entries],
+    options: CompareOptions,
+    keys: &dyn KeyNormalizer,
+    values: Option<&dyn ValueNormalizer>,
+) -> Vec<ConflictWarning> {
+    let prepared;
+    let overlays = match values {
+        Some(normalizer) => {
+            prepared = prepare_values(overlays, normalizer);
+            prepared.as_slice()
+        }
+        None => overlays,
+    };
+    let mut warnings = Vec::new();
+    let mut latest: HashMap<(usize, Cow<[String]>), &ValueWithLocation> = HashMap::new();
+    for values in precedence_order(overlays, options) {
+        for (path, value) in values.iter() {
+            let key = (value.document, path_key(path, keys));
+            if let Some(previous) = latest.get(&key)
+                && !value.suppressed
+                && previous.file != value.file
+                && !same_value(value, previous, options)
+            {
+                warnings.push(ConflictWarning {
+                    severity: Severity::Warning,
+                    file: value.file.clone(),
+                    path: path.clone(),
+                    line: value.line,
+                    column: value.column,
+                    value: value.value.clone(),
+                    previous_file: previous.file.clone(),
+                    previous_line: previous.line,
+                    previous_value: previous.value.clone(),
+                });
+            }
+            latest.insert(key, value);
+        }
+    }
+    warnings
+}
+
+/// The type `value` gives each node on its path, by depth from the root.
+fn node_types<'a>(
+    path: &'a [String],
+    value: &'a ValueWithLocation,
+) -> impl Iterator<Item = (usize, ValueType)> + 'a {
+    (1..=path.len()).map(move |depth| match value.parents.get(depth) {
+        Some(&parent) if depth < path.len() => (depth, parent),
+        _ => (depth, value.value_type),
+    })
+}
+
+/// Layers above the lowest whose values are all pointless overrides. Each
+/// value is flagged at most once, so comparing counts is enough.
+fn redundant_files(
+    all_values: &[Entries],
+    pointless: &[Override],
+    options: CompareOptions,
+) -> Vec<RedundantFile> {
+    precedence_order(all_values, options)
+        .into_iter()
+        .skip(1)
+        .filter_map(|values| {
+            let file = &values.first()?.1.file;
+            let flagged = pointless.iter().filter(|item| &item.file == file).count();
+            (flagged == values.len()).then(|| RedundantFile {
+                file: file.clone(),
+                values: values.len(),
+            })
+        })
+        .collect()
+}
+
+/// The outermost subtrees of layers above the lowest whose values, at least
+/// two, are all pointless overrides of earlier layers. Overrides are matched
+/// to values by where they are, so their paths are the ones reported.
+fn redundant_subtrees(
+    all_values: &[Entries],
+    pointless: &[Override],
+    options: CompareOptions,
+) -> Vec<RedundantSubtree> {
+    /// The values under one subtree, and the overrides among them
+    struct Tally<'a> {
+        values: usize,
+        flagged: Vec<&'a Override>,
+    }
+
+    let mut subtrees = Vec::new();
+    for values in precedence_order(all_values, options).into_iter().skip(1) {
+        let Some((_, first)) = values.first() else {
+            continue;
+        };
+        let flagged: HashMap<(usize, usize), &Override> = pointless
+            .iter()
+            .filter(|item| item.file == first.file && item.previous_file != item.file)
+            .map(|item| ((item.line, item.column), item))
+            .collect();
+        // By document and path, in the order the subtrees start
+        let mut tallies: Vec<Tally> = Vec::new();
+        let mut index: HashMap<(usize, &[String]), usize> = HashMap::new();
+        for (path, value) in values {
+            let item = flagged.get(&(value.line, value.column));
+            for depth in 1..path.len() {
+                let slot = *index
+                    .entry((value.document, &path[..depth]))
+                    .or_insert_with(|| {
+                        tallies.push(Tally {
+                            values: 0,
+                            flagged: Vec::new(),
+                        });
+                        tallies.len() - 1
+                    });
+                tallies[slot].values += 1;
+                tallies[slot].flagged.extend(item);
+            }
+        }
+        let redundant: HashSet<(usize, &[String])> = index
+            .iter()
+            .filter(|&(_, &slot)| {
+                let tally = &tallies[slot];
+                tally.values >= 2 && tally.flagged.len() == tally.values
+            })
+            .map(|(&key, _)| key)
+            .collect();
+        let mut outermost: Vec<(usize, &[String])> = redundant
+            .iter()
+            .filter(|&&(document, path)| {
+                !(1..path.len()).any(|depth| redundant.contains(&(document, &path[..depth])))
+            })
+            .copied()
+            .collect();
+        outermost.sort_by_key(|key| index[key]);
+        for key @ (_, path) in outermost {
+            let tally = &tallies[index[&key]];
+            let first = tally.flagged[0];
+            subtrees.push(RedundantSubtree {
+                severity: tally
+                    .flagged
+                    .iter()
+                    .map(|item| item.severity)
+                    .max()
+                    .unwrap_or(first.severity),
+                file: first.file.clone(),
+                path: first.path[..path.len()].to_vec(),
+                line: first.line,
+                column: first.column,
+                values: tally.values,
+            });
+        }
+    }
+    subtrees
+}
+
+/// Layers above the lowest that set nothing.
+fn empty_files(
+    all_values: &[Entries],
+    labels: &[String],
+    blank: &HashSet<String>,
+    options: CompareOptions,
+) -> Vec<EmptyFile> {
+    labelled_precedence_order(all_values, labels, options)
+        .into_iter()
+        .skip(1)
+        .filter(|(_, values)| values.is_empty())
+        .map(|(file, _)| EmptyFile {
+            file: file.clone(),
+            blank: blank.contains(file),
+        })
+        .collect()
+}
+
+/// [`precedence_order`] with each layer's label.
+fn labelled_precedence_order<'a>(
+    all_values: &'a [Entries],
+    labels: &'a [String],
+    options: CompareOptions,
+) -> Vec<(&'a String, &'a Entries)> {
+    let mut layers: Vec<_> = labels.iter().zip(all_values).collect();
+    if options.reverse_precedence {
+        layers.reverse();
+    }
+    layers
+}
+
+/// Distinct paths each layer above the lowest sets, and how many of them
+/// hold a pointless override. Layers that set nothing count as none of
+/// their paths being pointless.
+fn file_stats(
+    all_values: &[Entries],
+    labels: &[String],
+    pointless: &[Override],
+    options: CompareOptions,
+) -> Vec<FileStats> {
+    labelled_precedence_order(all_values, labels, options)
+        .into_iter()
+        .skip(1)
+        .map(|(file, values)| {
+            let flagged: HashSet<(&[String], usize, usize)> = pointless
+                .iter()
+                .filter(|item| &item.file == file)
+                .map(|item| (item.path.as_slice(), item.line, item.column))
+                .collect();
+            let mut paths = HashSet::new();
+            let mut pointless_paths = HashSet::new();
+            for (path, value) in values {
+                let key = (value.document, path.as_slice());
+                paths.insert(key);
+                if flagged.contains(&(path.as_slice(), value.line, value.column)) {
+                    pointless_paths.insert(key);
+                }
+            }
+            FileStats {
+                file: file.clone(),
+                total_paths: paths.len(),
+                pointless_paths: pointless_paths.len(),
+                redundancy_ratio: if paths.is_empty() {
+                    0.0
+                } else {
+                    pointless_paths.len() as f64 / paths.len() as f64
+                },
+            }
+        })
+        .collect()
+}
+
+/// Values nested more than `max_depth` keys or indices deep.
+fn find_deep_paths(
+    all_values: &[Vec<(Vec<String>, ValueWithLocation)>],
+    max_depth: usize,
+) -> Vec<DeepPathWarning> {
+    all_values
+        .iter()
+        .flatten()
+        .filter(|(path, value)| path.len() > max_depth && !value.suppressed)
+        .map(|(path, value)| DeepPathWarning {
+            severity: Severity::Info,
+            file: value.file.clone(),
+            path: path.clone(),
+            line: value.line,
+            column: value.column,
+            max_depth,
+        })
+        .collect()
+}
+
+/// The within-file and cross-file passes can both flag the same value. Keep
+/// one finding per location, preferring the one attributed to the earliest
+/// layer since that's where the value really comes from.
+fn dedupe_overrides(pointless: Vec<Override>, layers: &[&Entries]) -> Vec<Override> {
+    let layer = |file: &str| {
+        layers
+            .iter()
+            .position(|values| values.first().is_some_and(|(_, value)| value.file == file))
+            .unwrap_or(usize::MAX)
+    };
+
+    let mut deduped: Vec<Override> = Vec::with_capacity(pointless.len());
+    let mut seen: HashMap<(String, Vec<String>, usize, usize), usize> = HashMap::new();
+    for override_item in pointless {
+        let key = (
+            override_item.file.clone(),
+            override_item.path.clone(),
+            override_item.line,
+            override_item.column,
+        );
+        match seen.get(&key) {
+            Some(&index) => {
+                if layer(&override_item.previous_file) < layer(&deduped[index].previous_file) {
+                    deduped[index] = override_item;
+                }
+            }
+            None => {
+                seen.insert(key, deduped.len());
+                deduped.push(override_item);
+            }
+        }
+    }
+    deduped
 }
@@ -1,26 +1,964 @@
-use anyhow::Result;
-use clap::Parser as ClapParser;
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Parser as ClapParser, Subcommand, ValueEnum};
 use colored::Colorize;
-use pointless_pointer::PointlessPointer;
-use std::path::PathBuf;
+use config::{Config, DEFAULT_CONFIG};
+use ignore::WalkBuilder;
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::notify::RecursiveMode;
+use order::ValueFileOrder;
+use pointless_pointer::baseline::Baseline;
+use pointless_pointer::fix;
+use pointless_pointer::redact::Redaction;
+use pointless_pointer::report::{self, JsonReport};
+use pointless_pointer::{
+    Category, Finding, InputFormat, Override, PathProvenance, PointlessPointer, RedundantSubtree,
+    STDIN_LABEL, Severity, SortKey, SourceFile, format_path, is_url, sort_findings,
+};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::mpsc;
+use std::time::Duration;
+
+mod config;
+mod order;
 
 #[derive(ClapParser, Debug)]
 #[command(name = "pointless_pointer")]
 #[command(about = "Detect pointless overrides in Helm values files")]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Without a subcommand, the arguments of `analyze`
+    #[command(flatten)]
+    args: Args,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Report findings; what runs when no subcommand is given
+    Analyze(Args),
+    /// Delete pointless overrides from the override files, as --fix does
+    Fix(Args),
+    /// Print every path each file sets, with its value and line, without
+    /// comparing anything
+    List(Args),
+}
+
+#[derive(clap::Args, Debug)]
 struct Args {
-    /// Base values file
-    base: PathBuf,
+    /// Base values file (use `-` to read from stdin, or an `http(s)://` URL
+    /// to fetch it); required unless the config file sets `base` or
+    /// --helm-args is given
+    base: Option<PathBuf>,
 
-    /// Override files (can be specified multiple times with -f)
+    /// Override files (can be specified multiple times with -f, `-` reads stdin).
+    /// Glob patterns are expanded and sorted lexically; that order is the
+    /// override precedence, later files winning. `@list.txt` reads the files
+    /// from `list.txt`, one per line.
     #[arg(short = 'f', long = "file", value_name = "FILE")]
     overrides: Vec<PathBuf>,
+
+    /// Set a value on top of all files, as with `helm install --set`
+    /// (repeatable; `a.b=1,c[0]=x` and `list={a,b}` work as in Helm)
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Take every file from -f, as `helm install -f a.yaml -f b.yaml` does:
+    /// the first one is the base and later files win
+    #[arg(long, conflicts_with = "base")]
+    helm_args: bool,
+
+    /// Only check whether B restates values of A, without looking for keys
+    /// repeated within either file (unless --only asks for them)
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["A", "B"],
+        conflicts_with_all = ["base", "overrides", "helm_args", "dir", "defaults", "set"]
+    )]
+    compare: Vec<PathBuf>,
+
+    /// Directory to scan recursively for `*.yaml`/`*.yml` override files,
+    /// applied in sorted path order before any `-f` files
+    #[arg(long, value_name = "PATH")]
+    dir: Option<PathBuf>,
+
+    /// Order the override files as this file lists them, lowest precedence
+    /// first: a YAML list of files, or a manifest with a `valueFiles` list
+    /// such as an Argo CD application. Files it doesn't list are an error
+    #[arg(long, value_name = "FILE", conflicts_with = "compare")]
+    order_from: Option<PathBuf>,
+
+    /// With --order-from, apply files it doesn't list after the listed ones
+    /// instead of failing
+    #[arg(long, requires = "order_from")]
+    allow_extra: bool,
+
+    /// Maximum directory depth for --dir (1 = only files directly inside)
+    #[arg(long, value_name = "N", requires = "dir")]
+    depth: Option<usize>,
+
+    /// Also scan files that --dir would skip because of `.gitignore`,
+    /// `.ignore` or `.git/info/exclude`
+    #[arg(long, requires = "dir")]
+    no_ignore: bool,
+
+    /// Also scan hidden files and directories with --dir
+    #[arg(long, requires = "dir")]
+    hidden: bool,
+
+    /// Chart defaults values file, applied before the base file
+    #[arg(long, value_name = "FILE")]
+    defaults: Option<PathBuf>,
+
+    /// Compare values as exact strings rather than by YAML type; a quoted
+    /// number then differs from the same number unquoted
+    #[arg(long)]
+    strict_strings: bool,
+
+    /// Treat numbers at most E apart as equal when at least one is a float,
+    /// so `0.5` and `0.50000001` can count as the same. 0 compares exactly
+    #[arg(long, value_name = "E", default_value_t = 0.0, value_parser = parse_epsilon)]
+    float_epsilon: f64,
+
+    /// Match keys case-insensitively, so `Foo` and `foo` are the same path.
+    /// A file that uses both casings of a key will report them as duplicates
+    #[arg(long)]
+    case_insensitive_keys: bool,
+
+    /// Treat lists holding the same items in a different order as equal, so
+    /// reorder-only overrides are reported as pointless
+    #[arg(long)]
+    ignore_list_order: bool,
+
+    /// Expand `${VAR}` placeholders from the environment before comparing
+    /// values; unset variables are left as written, with a warning
+    #[arg(long)]
+    expand_env: bool,
+
+    /// Compare values under this dotted-path glob as Kubernetes quantities,
+    /// e.g. `500m` = `0.5` (repeatable; replaces the default `resources.*`
+    /// and `*.resources.*`)
+    #[arg(long = "quantity-path", value_name = "PATTERN")]
+    quantity_paths: Vec<String>,
+
+    /// Match elements of lists of mappings by this field instead of by index,
+    /// e.g. `name` for every list or `*env=name` for lists at paths matching
+    /// the glob (repeatable)
+    #[arg(long = "list-merge-key", value_name = "[PATTERN=]FIELD", value_parser = parse_merge_key)]
+    list_merge_keys: Vec<(String, String)>,
+
+    /// Treat these values as the same, e.g. `Always,always,ALWAYS`, or only
+    /// at paths matching a glob: `*.imagePullPolicy=Always,always`
+    /// (repeatable)
+    #[arg(long = "equivalent", value_name = "[PATTERN=]VALUE,VALUE", value_parser = parse_equivalence)]
+    equivalent: Vec<(String, Vec<String>)>,
+
+    /// Don't report keys repeated within files matching this glob, which are
+    /// still compared with other files (repeatable)
+    #[arg(long = "no-dup-check", value_name = "GLOB")]
+    no_dup_check: Vec<String>,
+
+    /// Suppress findings whose dotted path matches this glob (repeatable),
+    /// e.g. `image.*` or `*.replicaCount`, or whose path is exactly this
+    /// canonical path, e.g. `$.annotations["example.com/team"]`
+    #[arg(long = "ignore", value_name = "PATTERN")]
+    ignore: Vec<String>,
+
+    /// Print values as `*` (one per character) in every output format, so
+    /// secrets stay out of logs; paths and line numbers are kept
+    #[arg(long, conflicts_with_all = ["context", "dry_run"])]
+    redact: bool,
+
+    /// Only redact values at paths matching this dotted-path glob
+    /// (repeatable; implies --redact)
+    #[arg(long = "redact-path", value_name = "PATTERN", conflicts_with_all = ["context", "dry_run"])]
+    redact_paths: Vec<String>,
+
+    /// Maximum number of threads used to parse files (defaults to all cores)
+    #[arg(long, short = 'j', value_name = "N")]
+    jobs: Option<usize>,
+
+    /// Print to stderr how long reading, parsing each file and comparing
+    /// took, to find slow files
+    #[arg(long)]
+    timings: bool,
+
+    /// Follow each finding with a sentence explaining why it was reported
+    #[arg(long)]
+    explain: bool,
+
+    /// Show N lines of context around each pointless override and the value
+    /// it repeats
+    #[arg(long, value_name = "N")]
+    context: Option<usize>,
+
+    /// Never report `null` overrides as pointless, since they may be
+    /// intentional deletions
+    #[arg(long)]
+    null_deletes: bool,
+
+    /// When to use colors: `always` and `never` win over the NO_COLOR
+    /// environment variable, which wins over terminal auto-detection
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Also list overrides that do change a value, and what they changed
+    #[arg(long)]
+    show_effective: bool,
+
+    /// List every pointless override on its own, instead of a single finding
+    /// for a mapping or list whose values are all pointless
+    #[arg(long)]
+    no_rollup: bool,
+
+    /// Show every earlier file that set an overridden path, and point out
+    /// overrides that restore a value an intermediate file changed (implies
+    /// --show-effective)
+    #[arg(long)]
+    show_chain: bool,
+
+    /// Let earlier files take precedence over later ones, to find values in
+    /// the base that an override already sets
+    #[arg(long)]
+    reverse_precedence: bool,
+
+    /// Compare each file only with the file just before it, instead of with
+    /// the combined values of every earlier file
+    #[arg(long)]
+    pairwise: bool,
+
+    /// Baseline of acknowledged findings; only findings not in it are
+    /// reported and fail the run
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Write the current findings to the --baseline file instead of
+    /// reporting them
+    #[arg(long, requires = "baseline")]
+    write_baseline: bool,
+
+    /// Delete pointless cross-file overrides from the override files
+    #[arg(long)]
+    fix: bool,
+
+    /// With --fix or `fix`, show which lines would be removed without
+    /// writing
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Keep running and analyze again whenever an input file changes
+    #[arg(long, conflicts_with_all = ["fix", "write_baseline"])]
+    watch: bool,
+
+    /// Only look for these kinds of finding, e.g. `--only warnings,duplicates`
+    /// [default: all]
+    #[arg(long, value_enum, value_delimiter = ',')]
+    only: Vec<Only>,
+
+    /// Primary sort key for findings; the others break ties
+    #[arg(long, value_enum, default_value_t = SortOrder::File)]
+    sort: SortOrder,
+
+    /// Leave out findings less severe than this, so they neither show nor
+    /// fail the run
+    #[arg(long, value_enum, default_value_t = MinSeverity::Info)]
+    min_severity: MinSeverity,
+
+    /// Exit with status 1 when any finding is left after --ignore and the
+    /// baseline, to gate CI; implied by --baseline
+    #[arg(long)]
+    fail_on_findings: bool,
+
+    /// Whether duplicate key warnings fail the run when findings do
+    /// [default: true]
+    #[arg(long, value_name = "BOOL")]
+    fail_on_warnings: Option<bool>,
+
+    /// Show how many paths each override file sets and what fraction of
+    /// them are pointless
+    #[arg(long)]
+    stats: bool,
+
+    /// List, for every path, the file whose value wins and how many times
+    /// the path is set and overridden
+    #[arg(long)]
+    provenance: bool,
+
+    /// Warn when a file replaces a null with an empty string (`foo:` and
+    /// `foo: ""`), or the other way round
+    #[arg(long)]
+    warn_null_empty: bool,
+
+    /// Warn when two override files set the same path to different values,
+    /// so that which one wins depends only on the order they are given in
+    #[arg(long)]
+    warn_conflicts: bool,
+
+    /// List the base values that no override file sets, replaces or sets
+    /// anything under, to find configuration nothing needs
+    #[arg(long)]
+    dead_keys: bool,
+
+    /// Warn about values nested more than N keys deep (e.g. `a.b.c` is 3)
+    #[arg(long, value_name = "N")]
+    max_depth: Option<usize>,
+
+    /// Only compare values at or below this dotted path, e.g. `ingress`
+    #[arg(long, value_name = "PATH")]
+    root: Option<String>,
+
+    /// Show paths relative to --root
+    #[arg(long, requires = "root")]
+    relative_to_root: bool,
+
+    /// Also compare documents that are a single value, such as a file that
+    /// is just `hello`, under the path `(root)`
+    #[arg(long)]
+    include_root_scalars: bool,
+
+    /// Skip files that can't be read, listing them at the end, instead of
+    /// aborting the run
+    #[arg(long)]
+    skip_missing: bool,
+
+    /// Make each `---` document read from stdin (`-`) an override layer of
+    /// its own, in document order, labelled `<stdin>#0`, `<stdin>#1` and so on
+    #[arg(long)]
+    stdin_multidoc: bool,
+
+    /// Format of files whose name doesn't give it away, stdin included
+    /// [default: yaml]. `*.yaml`/`*.yml` are always YAML, and `.env`,
+    /// `.env.*` and `*.env` always dotenv
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    format_in: Option<SourceFormat>,
+
+    /// Keep parsed files in this directory, keyed by their content, so
+    /// unchanged files aren't parsed again on the next run
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Seconds to wait for each `http(s)://` file to download
+    #[arg(long, value_name = "SECONDS", default_value_t = 30)]
+    timeout: u64,
+
+    /// Trace how each file is parsed to stderr; repeat (`-vv`) to also log
+    /// every value recorded
+    #[arg(long, short = 'v', action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Print only the summary counts, and nothing for a clean run; with
+    /// --format json, only the `summary` object
+    #[arg(long, short = 'q', conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Output format [default: text, or github inside GitHub Actions]
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Repository URL that --format markdown links file locations to, as
+    /// `<URL>/<file>#L<line>` (e.g. `https://github.com/org/repo/blob/main`)
+    #[arg(long, value_name = "URL")]
+    repo_url: Option<String>,
+
+    /// Write the report to this file instead of stdout; needs a --format
+    /// other than text
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Config file to read settings from; by default `pointless_pointer.toml`
+    /// in the current directory is used if present. Command-line flags win
+    /// over its settings
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum OutputFormat {
+    /// Colored, human-readable text
+    Text,
+    /// A single JSON document on stdout
+    Json,
+    /// SARIF 2.1.0 log for code scanning
+    Sarif,
+    /// JUnit XML with one test suite per file
+    Junit,
+    /// GitHub-flavored Markdown tables, e.g. for pull request comments
+    Markdown,
+    /// CSV with one row per finding
+    Csv,
+    /// A self-contained HTML page with a collapsible section per file
+    Html,
+    /// GitHub Actions `::warning` commands, shown as annotations on the diff
+    Github,
+    /// The JSON document's contents as a single YAML document
+    Yaml,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum SortOrder {
+    /// Group by file, then path, then line
+    File,
+    /// By path, then file, then line
+    Path,
+    /// By line, then file, then path
+    Line,
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+impl From<SortOrder> for SortKey {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::File => SortKey::File,
+            SortOrder::Path => SortKey::Path,
+            SortOrder::Line => SortKey::Line,
+        }
+    }
+}
 
-    let analyzer = PointlessPointer::new(args.base, args.overrides);
-    let (pointless_overrides, warnings) = analyzer.analyze()?;
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Only {
+    /// Overrides restating the value of an earlier file
+    Overrides,
+    /// Keys repeated with the same value within a document
+    Duplicates,
+    /// Keys repeated with a different value within a document
+    Warnings,
+    /// Paths whose type changes between files
+    Types,
+}
+
+impl From<Only> for Category {
+    fn from(only: Only) -> Self {
+        match only {
+            Only::Overrides => Category::Overrides,
+            Only::Duplicates => Category::Duplicates,
+            Only::Warnings => Category::Warnings,
+            Only::Types => Category::Types,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum MinSeverity {
+    /// Everything, including values repeated within a file
+    Info,
+    /// Pointless overrides, type changes and worse
+    Warning,
+    /// Only keys given two different values in one file
+    Error,
+}
+
+impl From<MinSeverity> for Severity {
+    fn from(severity: MinSeverity) -> Self {
+        match severity {
+            MinSeverity::Info => Severity::Info,
+            MinSeverity::Warning => Severity::Warning,
+            MinSeverity::Error => Severity::Error,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum SourceFormat {
+    /// YAML documents
+    Yaml,
+    /// `KEY=VALUE` lines, `__` separating nested keys (`DB__HOST=db`)
+    Dotenv,
+    /// JSON, read as YAML but never rewritten by --fix
+    Json,
+}
+
+impl From<SourceFormat> for InputFormat {
+    fn from(format: SourceFormat) -> Self {
+        match format {
+            SourceFormat::Yaml => InputFormat::Yaml,
+            SourceFormat::Dotenv => InputFormat::Dotenv,
+            SourceFormat::Json => InputFormat::Json,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+fn main() -> Result<ExitCode> {
+    let Cli { command, args } = Cli::parse();
+    let (list, args) = match command {
+        None => (false, args),
+        Some(Command::Analyze(args)) => (false, args),
+        Some(Command::Fix(args)) => (false, Args { fix: true, ..args }),
+        Some(Command::List(args)) => (true, args),
+    };
+    // Checked here rather than by clap, since the `fix` subcommand implies
+    // --fix without the flag being given
+    if args.dry_run && !args.fix {
+        bail!("--dry-run needs --fix or the `fix` subcommand");
+    }
+    if args.watch && args.fix {
+        bail!("--watch can't be combined with fixing");
+    }
+    if args.fix
+        && args
+            .format
+            .is_some_and(|format| format != OutputFormat::Text)
+    {
+        bail!("--format can't be combined with fixing, which reports as text");
+    }
+
+    match args.color {
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+        ColorChoice::Auto => {
+            if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+                colored::control::set_override(false);
+            }
+        }
+    }
+
+    if list {
+        return list_values(&args);
+    }
+    if args.watch {
+        return watch(&args);
+    }
+    run(&args)
+}
+
+/// Print every value each layer sets, for the `list` subcommand.
+fn list_values(args: &Args) -> Result<ExitCode> {
+    let config = Config::find(args.config.as_deref())?;
+    let (base, overrides) = inputs(args, &config)?;
+    let mut analyzer = PointlessPointer::new(base, overrides)
+        .defaults(args.defaults.clone())
+        .skip_missing(args.skip_missing)
+        .input_format(args.format_in.map(Into::into))
+        .timeout(Some(Duration::from_secs(args.timeout)))
+        .jobs(args.jobs);
+    if !args.set.is_empty() {
+        analyzer = analyzer.set_values(&args.set)?;
+    }
+    let mut layers = analyzer.collect()?;
+    if args.redact || !args.redact_paths.is_empty() {
+        Redaction::new(&args.redact_paths)?.apply_to_values(&mut layers);
+    }
+    let output = match args.format.or(config.format).unwrap_or(OutputFormat::Text) {
+        OutputFormat::Text => {
+            let mut output = String::new();
+            for layer in &layers {
+                for (path, value, line) in &layer.values {
+                    output += &format!("{}:{line}: {} = {value}\n", layer.file, format_path(path));
+                }
+            }
+            output
+        }
+        OutputFormat::Json => report::values_to_json(&layers)? + "\n",
+        OutputFormat::Yaml => report::values_to_yaml(&layers)?,
+        _ => bail!("`list` can only print text, json or yaml"),
+    };
+    match &args.output {
+        Some(path) => write_output(path, &output)?,
+        None => print!("{output}"),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The base file and override files to analyze, from the command line or
+/// the config file.
+fn inputs(args: &Args, config: &Config) -> Result<(PathBuf, Vec<PathBuf>)> {
+    if let [a, b] = args.compare.as_slice() {
+        return Ok((a.clone(), vec![b.clone()]));
+    }
+    let override_patterns = if args.overrides.is_empty() {
+        &config.overrides
+    } else {
+        &args.overrides
+    };
+    let mut files = expand_overrides(override_patterns)?;
+    let base = if args.helm_args {
+        if files.is_empty() {
+            bail!("--helm-args needs at least one -f file to use as the base");
+        }
+        files.remove(0)
+    } else {
+        let Some(base) = args.base.clone().or(config.base.clone()) else {
+            bail!("No base file given: pass one or set `base` in {DEFAULT_CONFIG}");
+        };
+        base
+    };
+    let mut overrides = match &args.dir {
+        Some(dir) => scan_dir(dir, args.depth, &base, args.no_ignore, args.hidden)?,
+        None => Vec::new(),
+    };
+    overrides.extend(files);
+    if let Some(path) = &args.order_from {
+        overrides = ValueFileOrder::load(path)?.apply(overrides, args.allow_extra)?;
+    }
+    Ok((base, overrides))
+}
+
+/// Analyze the inputs once and report the findings.
+fn run(args: &Args) -> Result<ExitCode> {
+    let config = Config::find(args.config.as_deref())?;
+    let (base, overrides) = inputs(args, &config)?;
+    let ignore_patterns = if args.ignore.is_empty() {
+        &config.ignore
+    } else {
+        &args.ignore
+    };
+    // Inside GitHub Actions, annotate the diff unless a format was chosen.
+    // Fixing always reports as text, whatever the config or CI would pick
+    let in_github_actions = std::env::var("GITHUB_ACTIONS").is_ok_and(|value| value == "true");
+    let format = if args.fix {
+        OutputFormat::Text
+    } else {
+        args.format
+            .or(config.format)
+            .or(in_github_actions.then_some(OutputFormat::Github))
+            .unwrap_or(OutputFormat::Text)
+    };
+    let categories: Vec<Category> = if args.only.is_empty() && !args.compare.is_empty() {
+        vec![Category::Overrides]
+    } else if args.only.is_empty() {
+        vec![
+            Category::Overrides,
+            Category::Duplicates,
+            Category::Warnings,
+            Category::Types,
+        ]
+    } else {
+        args.only.iter().map(|&only| only.into()).collect()
+    };
+    if args.output.is_some() && format == OutputFormat::Text {
+        bail!("--output needs a --format other than text");
+    }
+    if format != OutputFormat::Text {
+        // Keep reports free of ANSI escapes, so they stay valid documents
+        colored::control::set_override(false);
+    }
+    // Chains are shown on the effective overrides
+    let show_effective = args.show_effective || args.show_chain;
+    let fail_on_findings = args.fail_on_findings
+        || args.baseline.is_some()
+        || config.fail_on_findings.unwrap_or(false);
+    let fail_on_warnings = args
+        .fail_on_warnings
+        .or(config.fail_on_warnings)
+        .unwrap_or(true);
+
+    let redaction = if args.redact || !args.redact_paths.is_empty() {
+        Some(Redaction::new(&args.redact_paths)?)
+    } else {
+        None
+    };
+    let mut analyzer = PointlessPointer::new(base, overrides)
+        .defaults(args.defaults.clone())
+        .strict_strings(args.strict_strings)
+        .float_epsilon(args.float_epsilon)
+        .null_deletes(args.null_deletes)
+        .case_insensitive_keys(args.case_insensitive_keys)
+        .ignore_list_order(args.ignore_list_order)
+        .expand_env(args.expand_env)
+        .show_effective(show_effective)
+        .show_chain(args.show_chain)
+        .stats(args.stats)
+        .warn_null_empty(args.warn_null_empty)
+        .warn_conflicts(args.warn_conflicts)
+        .dead_keys(args.dead_keys)
+        .provenance(args.provenance)
+        .rollup(!args.no_rollup)
+        .timings(args.timings)
+        .reverse_precedence(args.reverse_precedence)
+        .pairwise(args.pairwise)
+        .only(&categories)
+        .max_depth(args.max_depth)
+        .include_root_scalars(args.include_root_scalars)
+        .root(
+            &args
+                .root
+                .iter()
+                .flat_map(|root| root.split('.'))
+                .map(String::from)
+                .collect::<Vec<_>>(),
+        )
+        .relative_to_root(args.relative_to_root)
+        .skip_missing(args.skip_missing)
+        .input_format(args.format_in.map(Into::into))
+        .cache_dir(args.cache_dir.clone())
+        .timeout(Some(Duration::from_secs(args.timeout)))
+        .jobs(args.jobs)
+        .verbosity(args.verbose)
+        .progress(!args.quiet && io::stderr().is_terminal())
+        .stdin_multidoc(args.stdin_multidoc)
+        .ignore_paths(ignore_patterns)?;
+    if !args.quantity_paths.is_empty() {
+        analyzer = analyzer.quantity_paths(&args.quantity_paths)?;
+    }
+    if !args.list_merge_keys.is_empty() {
+        analyzer = analyzer.list_merge_keys(&args.list_merge_keys)?;
+    }
+    let equivalent = if args.equivalent.is_empty() {
+        config
+            .equivalent
+            .iter()
+            .map(|value| {
+                parse_equivalence(value)
+                    .map_err(|reason| anyhow!("Invalid equivalent `{value}` in config: {reason}"))
+            })
+            .collect::<Result<_>>()?
+    } else {
+        args.equivalent.clone()
+    };
+    if !args.no_dup_check.is_empty() {
+        analyzer = analyzer.no_dup_check(&args.no_dup_check)?;
+    }
+    if !equivalent.is_empty() {
+        analyzer = analyzer.equivalent_values(&equivalent)?;
+    }
+    if !args.set.is_empty() {
+        analyzer = analyzer.set_values(&args.set)?;
+    }
+    // Only context and fixes need the text afterwards; otherwise files are
+    // streamed through the parser instead of being read whole
+    let needs_content = args.context.is_some() || args.fix;
+    let (sources, mut skipped_files) = if needs_content {
+        analyzer.read_available_sources()?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    let mut findings: Vec<Finding> = if needs_content {
+        analyzer.findings_in(&sources)?.collect()
+    } else {
+        analyzer.findings()?.collect()
+    };
+    sort_findings(&mut findings, args.sort.into());
+    let min_severity = Severity::from(args.min_severity);
+    findings.retain(|finding| !finding.is_problem() || finding.severity() >= min_severity);
+    let mut suppressed = analyzer.suppressed();
+    if let Some(baseline_path) = &args.baseline {
+        if args.write_baseline {
+            let baseline = Baseline::from_findings(&findings);
+            baseline.save(baseline_path)?;
+            println!(
+                "Wrote {} finding(s) to baseline {}",
+                baseline.len(),
+                baseline_path.display()
+            );
+            return Ok(ExitCode::SUCCESS);
+        }
+        suppressed += Baseline::load(baseline_path)?.apply(&mut findings);
+    }
+    // Baselines match on values, so findings are masked once it's applied
+    if let Some(redaction) = &redaction {
+        redaction.apply(&mut findings);
+    }
+    let mut pointless_overrides = Vec::new();
+    let mut warnings = Vec::new();
+    let mut effective_overrides = Vec::new();
+    let mut deep_paths = Vec::new();
+    let mut type_mismatches = Vec::new();
+    let mut null_empty = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut redundant_files = Vec::new();
+    let mut redundant_subtrees = Vec::new();
+    let mut empty_files = Vec::new();
+    let mut file_stats = Vec::new();
+    let mut provenance = Vec::new();
+    let mut dead_keys = Vec::new();
+    for finding in findings {
+        match finding {
+            Finding::PointlessOverride(override_item) => pointless_overrides.push(override_item),
+            Finding::DuplicateKey(warning) => warnings.push(warning),
+            Finding::EffectiveOverride(override_item) => effective_overrides.push(override_item),
+            Finding::DeepPath(warning) => deep_paths.push(warning),
+            Finding::TypeMismatch(warning) => type_mismatches.push(warning),
+            Finding::NullEmpty(warning) => null_empty.push(warning),
+            Finding::Conflict(warning) => conflicts.push(warning),
+            Finding::RedundantFile(redundant) => redundant_files.push(redundant),
+            Finding::RedundantSubtree(subtree) => redundant_subtrees.push(subtree),
+            Finding::EmptyFile(empty) => empty_files.push(empty),
+            Finding::FileStats(stats) => file_stats.push(stats),
+            Finding::Provenance(path) => provenance.push(path),
+            Finding::DeadKey(dead) => dead_keys.push(dead),
+            Finding::SkippedFile(skipped) => skipped_files.push(skipped),
+        }
+    }
+
+    let (rolled_up, covered) = roll_up(&redundant_subtrees, &pointless_overrides);
+    // Reports list a rolled-up subtree in place of the overrides under it,
+    // as the text output does
+    let subtrees: Vec<RedundantSubtree> = rolled_up
+        .iter()
+        .map(|&(_, subtree)| subtree.clone())
+        .collect();
+    let listed_overrides: Vec<Override> = pointless_overrides
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !covered.contains(index))
+        .map(|(_, item)| item.clone())
+        .collect();
+
+    // When findings fail the run, any left at this point does, unless
+    // warnings are let through
+    let exit_code = if !fail_on_findings
+        || pointless_overrides.is_empty()
+            && ((warnings.is_empty()
+                && deep_paths.is_empty()
+                && type_mismatches.is_empty()
+                && null_empty.is_empty()
+                && conflicts.is_empty())
+                || !fail_on_warnings)
+    {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    };
+    // A fixed last line on stderr for log scrapers, whatever the format
+    let duplicates = pointless_overrides
+        .iter()
+        .filter(|item| item.file == item.previous_file)
+        .count();
+    let footer = format!(
+        "pointless_pointer: overrides={} warnings={} duplicates={duplicates}",
+        pointless_overrides.len() - duplicates,
+        warnings.len()
+            + deep_paths.len()
+            + type_mismatches.len()
+            + null_empty.len()
+            + conflicts.len(),
+    );
+
+    let findings = report::Findings {
+        pointless_overrides: &listed_overrides,
+        warnings: &warnings,
+        deep_paths: &deep_paths,
+        type_mismatches: &type_mismatches,
+        null_empty: &null_empty,
+        conflicts: &conflicts,
+        redundant_subtrees: &subtrees,
+    };
+    let report = match format {
+        OutputFormat::Text => None,
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let mut json_report = JsonReport::new(&listed_overrides, &warnings, suppressed)
+                .with_type_mismatches(&type_mismatches)
+                .with_redundant_files(&redundant_files)
+                .with_empty_files(&empty_files);
+            if show_effective {
+                json_report = json_report.with_effective_overrides(&effective_overrides);
+            }
+            if args.max_depth.is_some() {
+                json_report = json_report.with_deep_paths(&deep_paths);
+            }
+            if args.warn_null_empty {
+                json_report = json_report.with_null_empty(&null_empty);
+            }
+            if args.warn_conflicts {
+                json_report = json_report.with_conflicts(&conflicts);
+            }
+            if args.provenance {
+                json_report = json_report.with_provenance(&provenance);
+            }
+            if args.dead_keys {
+                json_report = json_report.with_dead_keys(&dead_keys);
+            }
+            if !args.no_rollup {
+                json_report = json_report.with_redundant_subtrees(&subtrees);
+            }
+            if args.stats {
+                json_report = json_report.with_file_stats(&file_stats);
+            }
+            if args.skip_missing {
+                json_report = json_report.with_skipped_files(&skipped_files);
+            }
+            Some(match (format, args.quiet) {
+                (OutputFormat::Json, true) => json_report.summary_to_json()? + "\n",
+                (OutputFormat::Json, false) => json_report.to_json()? + "\n",
+                (_, true) => json_report.summary_to_yaml()?,
+                (_, false) => json_report.to_yaml()?,
+            })
+        }
+        OutputFormat::Sarif => Some(report::to_sarif(&findings)? + "\n"),
+        OutputFormat::Junit => Some(report::to_junit(&analyzer.labels(), &findings) + "\n"),
+        OutputFormat::Markdown => Some(report::to_markdown(&findings, args.repo_url.as_deref())),
+        OutputFormat::Csv => Some(report::to_csv(&findings)?),
+        OutputFormat::Github => Some(report::to_github(&findings)),
+        OutputFormat::Html => Some(report::to_html(&analyzer.labels(), &findings)),
+    };
+    if let Some(report) = report {
+        match &args.output {
+            Some(output) => write_output(output, &report)?,
+            None => print!("{report}"),
+        }
+        eprintln!("{footer}");
+        return Ok(exit_code);
+    }
+
+    if args.quiet {
+        let counts = [
+            (
+                "Warning summary:",
+                warnings.len(),
+                "duplicate key warning(s)",
+            ),
+            ("Depth summary:", deep_paths.len(), "deeply nested value(s)"),
+            ("Type summary:", type_mismatches.len(), "type change(s)"),
+            (
+                "Null summary:",
+                null_empty.len(),
+                "null/empty string swap(s)",
+            ),
+            (
+                "Conflict summary:",
+                conflicts.len(),
+                "value(s) override files disagree on",
+            ),
+            (
+                "Summary:",
+                pointless_overrides.len(),
+                "pointless override(s) found",
+            ),
+            (
+                "Redundant:",
+                redundant_files.len(),
+                "file(s) that change nothing",
+            ),
+            ("Empty:", empty_files.len(), "file(s) that set no values"),
+            (
+                "Dead keys:",
+                dead_keys.len(),
+                "base value(s) no override touches",
+            ),
+            (
+                "Skipped:",
+                skipped_files.len(),
+                "file(s) that could not be read",
+            ),
+        ];
+        for (label, count, what) in counts {
+            if count > 0 {
+                println!("{} {count} {what}", label.bold());
+            }
+        }
+        for stats in &file_stats {
+            print!("{stats}");
+        }
+        if args.fix {
+            apply_fixes(&sources, &pointless_overrides, args.dry_run)?;
+        }
+        eprintln!("{footer}");
+        return Ok(exit_code);
+    }
 
     // Report warnings first
     if !warnings.is_empty() {
@@ -36,6 +974,9 @@ fn main() -> Result<()> {
 
         for warning in &warnings {
             print!("{warning}");
+            if args.explain {
+                print_explanation(&warning.explanation());
+            }
             println!();
         }
 
@@ -47,9 +988,104 @@ fn main() -> Result<()> {
         println!();
     }
 
+    if !deep_paths.is_empty() {
+        println!("{}", "⚠ Warnings - Values nested too deeply:".yellow());
+        println!();
+
+        for warning in &deep_paths {
+            print!("{warning}");
+            if args.explain {
+                print_explanation(&warning.explanation());
+            }
+            println!();
+        }
+
+        println!(
+            "{} {} deeply nested value(s)",
+            "Depth summary:".bold(),
+            deep_paths.len().to_string().yellow()
+        );
+        println!();
+    }
+
+    if !type_mismatches.is_empty() {
+        println!("{}", "⚠ Warnings - Values that change type:".yellow());
+        println!();
+
+        for warning in &type_mismatches {
+            print!("{warning}");
+            if args.explain {
+                print_explanation(&warning.explanation());
+            }
+            println!();
+        }
+
+        println!(
+            "{} {} type change(s)",
+            "Type summary:".bold(),
+            type_mismatches.len().to_string().yellow()
+        );
+        println!();
+    }
+
+    if !null_empty.is_empty() {
+        println!(
+            "{}",
+            "⚠ Warnings - Nulls and empty strings taken for each other:".yellow()
+        );
+        println!();
+
+        for warning in &null_empty {
+            print!("{warning}");
+            if args.explain {
+                print_explanation(&warning.explanation());
+            }
+            println!();
+        }
+
+        println!(
+            "{} {} null/empty string swap(s)",
+            "Null summary:".bold(),
+            null_empty.len().to_string().yellow()
+        );
+        println!();
+    }
+
+    if !conflicts.is_empty() {
+        println!(
+            "{}",
+            "⚠ Warnings - Override files that disagree, so their order decides:".yellow()
+        );
+        println!();
+
+        for warning in &conflicts {
+            print!("{warning}");
+            if args.explain {
+                print_explanation(&warning.explanation());
+            }
+            println!();
+        }
+
+        println!(
+            "{} {} value(s) override files disagree on",
+            "Conflict summary:".bold(),
+            conflicts.len().to_string().yellow()
+        );
+        println!();
+    }
+
     // Report pointless overrides
-    if pointless_overrides.is_empty() {
-        if warnings.is_empty() {
+    let no_warnings = warnings.is_empty()
+        && deep_paths.is_empty()
+        && type_mismatches.is_empty()
+        && null_empty.is_empty()
+        && conflicts.is_empty();
+    if !categories.contains(&Category::Overrides) && !categories.contains(&Category::Duplicates) {
+        if no_warnings {
+            println!("{}", "✓ No warnings found!".green());
+        }
+    } else if pointless_overrides.is_empty() {
+        if no_warnings {
             println!("{}", "✓ No pointless overrides found!".green());
         } else {
             println!(
@@ -61,8 +1097,34 @@ fn main() -> Result<()> {
         println!("{}", "⚠ Found pointless overrides:".yellow());
         println!();
 
-        for override_item in &pointless_overrides {
+        for (index, override_item) in pointless_overrides.iter().enumerate() {
+            if let Some((_, subtree)) = rolled_up.iter().find(|&&(first, _)| first == index) {
+                print!("{subtree}");
+                if args.explain {
+                    print_explanation(&subtree.explanation());
+                }
+                if let Some(context) = args.context {
+                    print_context(&sources, &subtree.file, subtree.line, context);
+                }
+                println!();
+                continue;
+            }
+            if covered.contains(&index) {
+                continue;
+            }
             print!("{override_item}");
+            if args.explain {
+                print_explanation(&override_item.explanation());
+            }
+            if let Some(context) = args.context {
+                print_context(&sources, &override_item.file, override_item.line, context);
+                print_context(
+                    &sources,
+                    &override_item.previous_file,
+                    override_item.previous_line,
+                    context,
+                );
+            }
             println!();
         }
 
@@ -71,7 +1133,463 @@ fn main() -> Result<()> {
             "Summary:".bold(),
             pointless_overrides.len().to_string().red()
         );
+
+        if !redundant_files.is_empty() {
+            println!();
+            println!("{}", "ℹ Files that can be deleted:".cyan());
+            for redundant in &redundant_files {
+                print!("{redundant}");
+            }
+        }
+    }
+
+    if !empty_files.is_empty() {
+        println!();
+        println!("{}", "ℹ Files that set nothing:".cyan());
+        for empty in &empty_files {
+            print!("{empty}");
+        }
+    }
+
+    if show_effective {
+        println!();
+        if effective_overrides.is_empty() {
+            println!("{}", "No overrides change an effective value".dimmed());
+        } else {
+            println!("{}", "ℹ Effective overrides:".cyan());
+            println!();
+
+            for override_item in &effective_overrides {
+                print!("{override_item}");
+                println!();
+            }
+
+            println!(
+                "{} {} override(s) change an effective value",
+                "Effective:".bold(),
+                effective_overrides.len().to_string().cyan()
+            );
+        }
+    }
+
+    if args.stats {
+        println!();
+        println!("{}", "ℹ Pointless paths per file:".cyan());
+        for stats in &file_stats {
+            print!("{stats}");
+        }
+    }
+
+    if args.provenance {
+        println!();
+        println!("{}", "ℹ Where each value comes from:".cyan());
+        print_provenance(&provenance);
     }
 
+    if args.dead_keys {
+        println!();
+        if dead_keys.is_empty() {
+            println!(
+                "{}",
+                "Every base value is set or replaced by an override".dimmed()
+            );
+        } else {
+            println!("{}", "ℹ Base values no override touches:".cyan());
+            for dead in &dead_keys {
+                print!("{dead}");
+            }
+        }
+    }
+
+    if suppressed > 0 {
+        println!(
+            "{} {} finding(s) matched --ignore patterns or the baseline",
+            "Suppressed:".bold(),
+            suppressed.to_string().dimmed()
+        );
+    }
+
+    if !skipped_files.is_empty() {
+        println!();
+        println!("{}", "⚠ Skipped files that could not be read:".yellow());
+        println!();
+        for skipped in &skipped_files {
+            print!("{skipped}");
+            println!();
+        }
+    }
+
+    if args.fix {
+        println!();
+        apply_fixes(&sources, &pointless_overrides, args.dry_run)?;
+    }
+
+    eprintln!("{footer}");
+    Ok(exit_code)
+}
+
+/// `PATTERN=VALUE,VALUE,...`, or just the values for any path.
+fn parse_equivalence(value: &str) -> Result<(String, Vec<String>), String> {
+    let (pattern, class) = value.split_once('=').unwrap_or(("*", value));
+    let class: Vec<String> = class.split(',').map(str::to_string).collect();
+    if class.len() < 2 {
+        return Err("at least two comma-separated values are needed".to_string());
+    }
+    Ok((pattern.to_string(), class))
+}
+
+/// `PATTERN=FIELD`, or a bare `FIELD` for lists anywhere.
+fn parse_merge_key(value: &str) -> Result<(String, String), String> {
+    let (pattern, field) = value.rsplit_once('=').unwrap_or(("*", value));
+    if field.is_empty() {
+        return Err("the field name is empty".to_string());
+    }
+    Ok((pattern.to_string(), field.to_string()))
+}
+
+fn parse_epsilon(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(epsilon) if epsilon.is_finite() && epsilon >= 0.0 => Ok(epsilon),
+        _ => Err("expected a non-negative number".to_string()),
+    }
+}
+
+/// Run the analysis again after every change to an input, until interrupted.
+/// The directories holding the inputs are watched rather than the files
+/// themselves, so a file an editor replaces by renaming a new copy over it is
+/// still followed. Reading a file raises events too, so only a new
+/// modification time or size counts as a change.
+fn watch(args: &Args) -> Result<ExitCode> {
+    let config = Config::find(args.config.as_deref())?;
+    let (base, overrides) = inputs(args, &config)?;
+    let mut files = HashSet::new();
+    let mut dirs = HashSet::new();
+    for path in std::iter::once(&base)
+        .chain(&overrides)
+        .chain(&args.defaults)
+    {
+        if path.as_os_str() == "-" {
+            bail!("--watch can't be used with `-` (stdin)");
+        }
+        let (Some(name), false) = (path.file_name(), is_url(path)) else {
+            continue;
+        };
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let dir = dir
+            .canonicalize()
+            .with_context(|| format!("Failed to watch {}", dir.display()))?;
+        files.insert(dir.join(name));
+        dirs.insert(dir);
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer = new_debouncer(Duration::from_millis(200), sender)?;
+    for dir in &dirs {
+        debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)?;
+    }
+    // New files under --dir count too, so watch all of it
+    let scanned = match &args.dir {
+        Some(dir) => {
+            debouncer.watcher().watch(dir, RecursiveMode::Recursive)?;
+            Some(dir.canonicalize()?)
+        }
+        None => None,
+    };
+    let is_input = |path: &Path| {
+        files.contains(path)
+            || scanned.as_ref().is_some_and(|dir| {
+                path.starts_with(dir)
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension == "yaml" || extension == "yml")
+            })
+    };
+
+    let stamp = |path: &Path| {
+        fs::metadata(path)
+            .ok()
+            .map(|metadata| (metadata.modified().ok(), metadata.len()))
+    };
+    let mut stamps: HashMap<PathBuf, _> = files
+        .iter()
+        .map(|file| (file.clone(), stamp(file)))
+        .collect();
+
+    loop {
+        // Clear the screen and start at the top
+        print!("\x1B[2J\x1B[H");
+        if let Err(error) = run(args) {
+            eprintln!("Error: {error:#}");
+        }
+        println!();
+        println!("{}", "Watching for changes (Ctrl-C to stop)".dimmed());
+
+        loop {
+            match receiver.recv()? {
+                Ok(events) => {
+                    let mut changed = false;
+                    for event in events.iter().filter(|event| is_input(&event.path)) {
+                        let current = stamp(&event.path);
+                        changed |= stamps.insert(event.path.clone(), current) != Some(current);
+                    }
+                    if changed {
+                        break;
+                    }
+                }
+                Err(error) => eprintln!("Watch error: {error}"),
+            }
+        }
+    }
+}
+
+/// Remove pointless overrides from their files, or preview that with `dry_run`.
+fn apply_fixes(
+    sources: &[SourceFile],
+    pointless_overrides: &[Override],
+    dry_run: bool,
+) -> Result<()> {
+    let fixes = fix::plan_fixes(sources, pointless_overrides)?;
+    if fixes.is_empty() {
+        println!("{}", "Nothing to fix".dimmed());
+        return Ok(());
+    }
+
+    for file_fix in &fixes {
+        let Some(source) = sources.iter().find(|source| source.label == file_fix.file) else {
+            continue;
+        };
+        let count = file_fix.removed_lines.len();
+        if dry_run {
+            println!(
+                "{} {count} line(s) from {}:",
+                "Would remove".bold(),
+                file_fix.file
+            );
+            for line in &file_fix.removed_lines {
+                let text = source.content.lines().nth(line - 1).unwrap_or_default();
+                println!("  {} {line:>4} | {text}", "-".red());
+            }
+        } else {
+            file_fix.write(source)?;
+            println!(
+                "{} {count} line(s) from {}",
+                "Removed".bold(),
+                file_fix.file
+            );
+        }
+    }
     Ok(())
 }
+
+/// Write a report to `path` through a temporary file beside it, renamed into
+/// place once complete, so a failed write never leaves half a report behind.
+fn write_output(path: &Path, content: &str) -> Result<()> {
+    let name = path
+        .file_name()
+        .with_context(|| format!("{} is not a file name", path.display()))?;
+    let mut temporary_name = std::ffi::OsString::from(".");
+    temporary_name.push(name);
+    temporary_name.push(format!(".{}.tmp", std::process::id()));
+    let temporary = path.with_file_name(temporary_name);
+    let written = fs::write(&temporary, content).and_then(|()| fs::rename(&temporary, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    written.with_context(|| format!("Failed to write {}", path.display()))?;
+    eprintln!("Wrote report to {}", path.display());
+    Ok(())
+}
+
+/// A table of every path with the file that wins it, sorted by path.
+fn print_provenance(provenance: &[PathProvenance]) {
+    let mut rows: Vec<(String, String, usize, usize)> = provenance
+        .iter()
+        .map(|path| {
+            (
+                format_path(&path.path),
+                format!("{}:{}", path.file, path.line),
+                path.writes,
+                path.overridden,
+            )
+        })
+        .collect();
+    rows.sort();
+    let header = ("PATH", "WINNER", "SET", "OVERRIDDEN");
+    let path_width = rows
+        .iter()
+        .map(|row| row.0.len())
+        .fold(header.0.len(), usize::max);
+    let file_width = rows
+        .iter()
+        .map(|row| row.1.len())
+        .fold(header.1.len(), usize::max);
+    println!(
+        "  {:path_width$}  {:file_width$}  {:>3}  {:>10}",
+        header.0.bold(),
+        header.1.bold(),
+        header.2.bold(),
+        header.3.bold()
+    );
+    for (path, file, writes, overridden) in rows {
+        println!("  {path:path_width$}  {file:file_width$}  {writes:>3}  {overridden:>10}");
+    }
+}
+
+/// The subtrees to show in place of the overrides under them, each with the
+/// index of its first override, and the indexes of all those overrides. A
+/// subtree only stands in when every override under it is still reported
+/// after ignore patterns, baselines and `--min-severity`.
+fn roll_up<'a>(
+    subtrees: &'a [RedundantSubtree],
+    overrides: &[Override],
+) -> (Vec<(usize, &'a RedundantSubtree)>, HashSet<usize>) {
+    let mut rolled_up = Vec::new();
+    let mut covered = HashSet::new();
+    for subtree in subtrees {
+        let under: Vec<usize> = overrides
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                item.file == subtree.file
+                    && item.previous_file != item.file
+                    && item.path.starts_with(&subtree.path)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        if under.len() != subtree.values || under.iter().any(|index| covered.contains(index)) {
+            continue;
+        }
+        rolled_up.push((under[0], subtree));
+        covered.extend(under);
+    }
+    (rolled_up, covered)
+}
+
+/// `  Why: ...` below a finding.
+fn print_explanation(explanation: &str) {
+    println!("  {} {explanation}", "Why:".bold());
+}
+
+/// Print the lines around `line` in the named source, highlighting `line`.
+fn print_context(sources: &[SourceFile], label: &str, line: usize, context: usize) {
+    // A document of stdin split with --stdin-multidoc counts lines from the
+    // start of stdin
+    let file = match label.rsplit_once('#') {
+        Some((STDIN_LABEL, _)) => STDIN_LABEL,
+        _ => label,
+    };
+    let Some(source) = sources.iter().find(|source| source.label == file) else {
+        return;
+    };
+
+    println!("  {}", format!("{label}:").dimmed());
+    for (number, text) in source.lines_around(line, context) {
+        if number == line {
+            println!("  {} {number:>4} | {}", ">".red().bold(), text.bold());
+        } else {
+            println!("    {number:>4} | {}", text.dimmed());
+        }
+    }
+}
+
+/// Expand glob patterns among the override arguments, keeping plain paths
+/// as-is, and `@list` arguments into the files their list names.
+fn expand_overrides(patterns: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut overrides = Vec::new();
+    for pattern in patterns {
+        let pattern_str = pattern.to_string_lossy();
+        if let Some(list) = pattern_str.strip_prefix('@') {
+            overrides.extend(expand_overrides(&read_override_list(Path::new(list))?)?);
+            continue;
+        }
+        // URLs are fetched as-is, query string and all
+        if is_url(pattern) || !pattern_str.contains(['*', '?', '[']) {
+            overrides.push(pattern.clone());
+            continue;
+        }
+
+        let mut matches = glob::glob(&pattern_str)
+            .with_context(|| format!("Invalid glob pattern: {pattern_str}"))?
+            .collect::<Result<Vec<_>, _>>()?;
+        if matches.is_empty() {
+            bail!("Glob pattern matched no files: {pattern_str}");
+        }
+        matches.sort();
+        overrides.extend(matches);
+    }
+    Ok(overrides)
+}
+
+/// The override files, or glob patterns, listed one per line in `path`, in
+/// precedence order. Blank lines and lines starting with `#` are skipped, and
+/// relative paths are taken relative to the list's directory. A listed
+/// `@file` is taken as a file name, so lists don't nest.
+fn read_override_list(path: &Path) -> Result<Vec<PathBuf>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read override list {}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let file = PathBuf::from(line);
+            let file = if line.starts_with('@') {
+                PathBuf::from(".").join(file)
+            } else {
+                file
+            };
+            if line == "-" || is_url(&file) {
+                file
+            } else {
+                dir.join(file)
+            }
+        })
+        .collect())
+}
+
+/// Collect YAML files under `dir` in sorted path order, skipping the base file.
+/// Symlinks are not followed, so link cycles can't trap the walk. Unless told
+/// otherwise, files excluded by git ignore rules and hidden files are left out.
+fn scan_dir(
+    dir: &Path,
+    depth: Option<usize>,
+    base: &Path,
+    no_ignore: bool,
+    hidden: bool,
+) -> Result<Vec<PathBuf>> {
+    let base = base.canonicalize().ok();
+    let walker = WalkBuilder::new(dir)
+        .follow_links(false)
+        .max_depth(depth)
+        .standard_filters(!no_ignore)
+        .hidden(!hidden)
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = entry.with_context(|| format!("Failed to scan {}", dir.display()))?;
+        let path = entry.path();
+        let is_yaml = path
+            .extension()
+            .is_some_and(|extension| extension == "yaml" || extension == "yml");
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+            || !is_yaml
+        {
+            continue;
+        }
+        if base.is_some() && path.canonicalize().ok() == base {
+            continue;
+        }
+        files.push(path.to_path_buf());
+    }
+    files.sort();
+    Ok(files)
+}
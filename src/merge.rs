@@ -0,0 +1,234 @@
+//! The values in effect once every layer is applied, as Helm merges them,
+//! written back out as YAML.
+
+use crate::{Entries, ValueType, ValueWithLocation, normalize};
+use saphyr_parser::ScalarStyle;
+use std::collections::BTreeMap;
+
+/// A layer's values as a tree. Scalars, lists of scalars and empty
+/// collections are leaves, as the collector records them.
+enum Node<'a> {
+    Collection {
+        kind: ValueType,
+        entries: Vec<(String, Node<'a>)>,
+    },
+    Leaf(&'a ValueWithLocation),
+}
+
+impl<'a> Node<'a> {
+    fn insert(&mut self, path: &[String], parents: &[ValueType], value: &'a ValueWithLocation) {
+        let Some((segment, rest)) = path.split_first() else {
+            *self = Node::Leaf(value);
+            return;
+        };
+        let kind = parents.first().copied().unwrap_or(ValueType::Mapping);
+        if !matches!(self, Node::Collection { kind: current, .. } if *current == kind) {
+            *self = Node::Collection {
+                kind,
+                entries: Vec::new(),
+            };
+        }
+        let Node::Collection { entries, .. } = self else {
+            unreachable!("replaced by a collection above");
+        };
+        let index = match entries.iter().position(|(key, _)| key == segment) {
+            Some(index) => index,
+            None => {
+                entries.push((
+                    segment.clone(),
+                    Node::Collection {
+                        kind: ValueType::Mapping,
+                        entries: Vec::new(),
+                    },
+                ));
+                entries.len() - 1
+            }
+        };
+        entries[index]
+            .1
+            .insert(rest, parents.get(1..).unwrap_or_default(), value);
+    }
+}
+
+/// Apply `over` on top of `base` the way Helm does: mappings merge key by
+/// key, while anything else, lists included, replaces what was there. A
+/// null removes the key and `{}` leaves a mapping as it is.
+fn merge<'a>(base: Node<'a>, over: Node<'a>) -> Option<Node<'a>> {
+    match (base, over) {
+        (_, Node::Leaf(value)) if value.is_null => None,
+        (
+            base @ Node::Collection {
+                kind: ValueType::Mapping,
+                ..
+            },
+            Node::Leaf(value),
+        ) if value.value_type == ValueType::Mapping => Some(base),
+        (
+            Node::Collection {
+                kind: ValueType::Mapping,
+                entries: mut merged,
+            },
+            Node::Collection {
+                kind: ValueType::Mapping,
+                entries,
+            },
+        ) => {
+            for (key, node) in entries {
+                match merged.iter().position(|(existing, _)| *existing == key) {
+                    Some(index) => {
+                        let (_, previous) = merged.remove(index);
+                        if let Some(node) = merge(previous, node) {
+                            merged.insert(index, (key, node));
+                        }
+                    }
+                    None => merged.extend(without_nulls(node).map(|node| (key, node))),
+                }
+            }
+            Some(Node::Collection {
+                kind: ValueType::Mapping,
+                entries: merged,
+            })
+        }
+        (_, over) => without_nulls(over),
+    }
+}
+
+/// `node` with the nulls in its mappings left out, as Helm drops them.
+fn without_nulls(node: Node<'_>) -> Option<Node<'_>> {
+    match node {
+        Node::Leaf(value) if value.is_null => None,
+        Node::Collection {
+            kind: ValueType::Mapping,
+            entries,
+        } => Some(Node::Collection {
+            kind: ValueType::Mapping,
+            entries: entries
+                .into_iter()
+                .filter_map(|(key, node)| Some((key, without_nulls(node)?)))
+                .collect(),
+        }),
+        node => Some(node),
+    }
+}
+
+/// Every document of `layers`, lowest priority first, merged and written as
+/// YAML. Each value is followed by a comment naming the file and line that
+/// set it.
+pub(crate) fn merged_yaml(layers: &[&Entries]) -> String {
+    let mut documents: BTreeMap<usize, Option<Node>> = BTreeMap::new();
+    for (layer, values) in layers.iter().enumerate() {
+        let mut trees: BTreeMap<usize, Node> = BTreeMap::new();
+        for (path, value) in values.iter() {
+            trees
+                .entry(value.document)
+                .or_insert(Node::Collection {
+                    kind: ValueType::Mapping,
+                    entries: Vec::new(),
+                })
+                .insert(path, &value.parents, value);
+        }
+        for (document, tree) in trees {
+            let merged = match documents.remove(&document).flatten() {
+                // The lowest layer's nulls are values of their own
+                None if layer == 0 => Some(tree),
+                None => without_nulls(tree),
+                Some(previous) => merge(previous, tree),
+            };
+            documents.insert(document, merged);
+        }
+    }
+
+    let mut out = String::new();
+    for (index, tree) in documents.into_values().enumerate() {
+        if index > 0 {
+            out.push_str("---\n");
+        }
+        match tree {
+            Some(Node::Leaf(value)) => {
+                out.push_str(&format!("{}{}\n", render_scalar(value), provenance(value)))
+            }
+            Some(Node::Collection { entries, .. }) if entries.is_empty() => out.push_str("{}\n"),
+            Some(node) => write_node(&mut out, &node, 0),
+            None => out.push_str("null\n"),
+        }
+    }
+    out
+}
+
+fn write_node(out: &mut String, node: &Node, indent: usize) {
+    let Node::Collection { kind, entries } = node else {
+        return;
+    };
+    let pad = " ".repeat(indent);
+    for (key, child) in entries {
+        let prefix = match kind {
+            ValueType::Sequence => format!("{pad}-"),
+            _ => format!("{pad}{}:", render_key(key)),
+        };
+        match child {
+            Node::Leaf(value) => out.push_str(&format!(
+                "{prefix} {}{}\n",
+                render_scalar(value),
+                provenance(value)
+            )),
+            // A collection in a list starts on the dash's line
+            Node::Collection { .. } if *kind == ValueType::Sequence => {
+                let mut nested = String::new();
+                write_node(&mut nested, child, indent + 2);
+                out.push_str(&prefix);
+                out.push(' ');
+                out.push_str(&nested[indent + 2..]);
+            }
+            Node::Collection { .. } => {
+                out.push_str(&prefix);
+                out.push('\n');
+                write_node(out, child, indent + 2);
+            }
+        }
+    }
+}
+
+fn provenance(value: &ValueWithLocation) -> String {
+    format!("  # from {}:{}", value.file, value.line)
+}
+
+/// A key as it can be written in block style, quoted unless it is plain
+/// text that reads back as the same string.
+fn render_key(key: &str) -> String {
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'))
+        && !key.starts_with('-')
+        && normalize::value_type(key, ScalarStyle::Plain, None) == ValueType::String;
+    if plain {
+        key.to_string()
+    } else {
+        serde_json::to_string(key).unwrap_or_default()
+    }
+}
+
+/// A value as written, quoted when it was, so that it reads back with the
+/// same type. Block scalars become double-quoted strings.
+fn render_scalar(value: &ValueWithLocation) -> String {
+    if value.items.is_some() || matches!(value.value_type, ValueType::Mapping | ValueType::Sequence)
+    {
+        return value.value.clone();
+    }
+    let resolved = normalize::value_type(&value.value, ScalarStyle::Plain, None);
+    match value.style {
+        ScalarStyle::Plain if resolved == value.value_type => value.value.clone(),
+        ScalarStyle::SingleQuoted if value.value_type == ValueType::String => {
+            format!("'{}'", value.value.replace('\'', "''"))
+        }
+        _ if value.value_type == ValueType::String => {
+            serde_json::to_string(&value.value).unwrap_or_default()
+        }
+        // A tag made it something its spelling doesn't say
+        _ => format!(
+            "!!{} {}",
+            value.value_type,
+            serde_json::to_string(&value.value).unwrap_or_default()
+        ),
+    }
+}
@@ -0,0 +1,228 @@
+//! Canonical forms for YAML scalars so equivalent spellings compare equal.
+
+use crate::ValueType;
+use saphyr_parser::{ScalarStyle, Tag};
+use std::borrow::Cow;
+
+/// A scalar classified with YAML 1.1 resolution rules.
+#[derive(Debug, Clone)]
+pub(crate) enum Scalar<'a> {
+    Null,
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    Str(&'a str),
+}
+
+impl PartialEq for Scalar<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Scalar::Null, Scalar::Null) => true,
+            (Scalar::Bool(a), Scalar::Bool(b)) => a == b,
+            (Scalar::Int(a), Scalar::Int(b)) => a == b,
+            (Scalar::Float(a), Scalar::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Scalar::Int(a), Scalar::Float(b)) | (Scalar::Float(b), Scalar::Int(a)) => {
+                *a as f64 == *b
+            }
+            (Scalar::Str(a), Scalar::Str(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Classify a raw scalar as bool/int/float/null/string.
+pub(crate) fn classify(raw: &str) -> Scalar<'_> {
+    match raw {
+        "" | "~" | "null" | "Null" | "NULL" => return Scalar::Null,
+        "y" | "Y" | "yes" | "Yes" | "YES" | "true" | "True" | "TRUE" | "on" | "On" | "ON" => {
+            return Scalar::Bool(true);
+        }
+        "n" | "N" | "no" | "No" | "NO" | "false" | "False" | "FALSE" | "off" | "Off" | "OFF" => {
+            return Scalar::Bool(false);
+        }
+        _ => {}
+    }
+
+    if let Some(int) = parse_int(raw) {
+        return Scalar::Int(int);
+    }
+    if let Some(float) = parse_float(raw) {
+        return Scalar::Float(float);
+    }
+    Scalar::Str(raw)
+}
+
+/// Whether a scalar event denotes null, as opposed to e.g. a quoted empty
+/// string: either tagged `!!null` or a plain null spelling.
+pub(crate) fn is_null(raw: &str, style: ScalarStyle, tag: Option<&Tag>) -> bool {
+    match tag {
+        Some(tag) => tag.is_yaml_core_schema() && tag.suffix == "null",
+        None => style == ScalarStyle::Plain && matches!(classify(raw), Scalar::Null),
+    }
+}
+
+/// The type a scalar event resolves to. Quoted and block scalars are strings;
+/// tags outside the core schema are taken as strings too.
+pub(crate) fn value_type(raw: &str, style: ScalarStyle, tag: Option<&Tag>) -> ValueType {
+    match tag {
+        Some(tag) if tag.is_yaml_core_schema() => match tag.suffix.as_str() {
+            "null" => ValueType::Null,
+            "bool" => ValueType::Bool,
+            "int" => ValueType::Int,
+            "float" => ValueType::Float,
+            _ => ValueType::String,
+        },
+        Some(_) => ValueType::String,
+        None if style != ScalarStyle::Plain => ValueType::String,
+        None => match classify(raw) {
+            Scalar::Null => ValueType::Null,
+            Scalar::Bool(_) => ValueType::Bool,
+            Scalar::Int(_) => ValueType::Int,
+            Scalar::Float(_) => ValueType::Float,
+            Scalar::Str(_) => ValueType::String,
+        },
+    }
+}
+
+/// One spelling for each value a raw scalar can resolve to, so that two
+/// scalars are [`equivalent`] when their canonical forms are equal. Floats
+/// that are whole numbers are written as integers.
+pub(crate) fn canonical(raw: &str) -> Cow<'_, str> {
+    let canonical = match classify(raw) {
+        Scalar::Null => "null".to_string(),
+        Scalar::Bool(bool) => bool.to_string(),
+        Scalar::Int(int) => int.to_string(),
+        Scalar::Float(float) if float.is_nan() => ".nan".to_string(),
+        Scalar::Float(float) if float.is_infinite() => {
+            if float > 0.0 { ".inf" } else { "-.inf" }.to_string()
+        }
+        Scalar::Float(float) if float.fract() == 0.0 && float.abs() < i128::MAX as f64 => {
+            (float as i128).to_string()
+        }
+        Scalar::Float(float) => float.to_string(),
+        Scalar::Str(_) => return Cow::Borrowed(raw),
+    };
+    if canonical == raw {
+        Cow::Borrowed(raw)
+    } else {
+        Cow::Owned(canonical)
+    }
+}
+
+/// Whether two raw scalars are the same value once normalized.
+pub(crate) fn equivalent(a: &str, b: &str) -> bool {
+    a == b || classify(a) == classify(b)
+}
+
+/// Whether two raw scalars are numbers, at least one of them a float, that
+/// are no more than `epsilon` apart. NaN is never within reach of anything.
+pub(crate) fn within(a: &str, b: &str, epsilon: f64) -> bool {
+    let number = |raw| match classify(raw) {
+        Scalar::Int(int) => Some((int as f64, false)),
+        Scalar::Float(float) => Some((float, true)),
+        _ => None,
+    };
+    match (number(a), number(b)) {
+        (Some((a, a_float)), Some((b, b_float))) => {
+            (a_float || b_float) && (a - b).abs() <= epsilon
+        }
+        _ => false,
+    }
+}
+
+fn split_sign(raw: &str) -> (i128, &str) {
+    match raw.as_bytes().first() {
+        Some(b'-') => (-1, &raw[1..]),
+        Some(b'+') => (1, &raw[1..]),
+        _ => (1, raw),
+    }
+}
+
+fn parse_int(raw: &str) -> Option<i128> {
+    let (sign, body) = split_sign(raw);
+    let digits: String = body.chars().filter(|c| *c != '_').collect();
+    if digits.is_empty() || body.starts_with('_') {
+        return None;
+    }
+
+    let magnitude = if let Some(bin) = digits.strip_prefix("0b") {
+        i128::from_str_radix(bin, 2).ok()?
+    } else if let Some(hex) = digits.strip_prefix("0x") {
+        i128::from_str_radix(hex, 16).ok()?
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        i128::from_str_radix(&digits[1..], 8).ok()?
+    } else if digits.contains(':') {
+        // Sexagesimal, e.g. 1:30 == 90
+        let mut parts = digits.split(':');
+        let mut total: i128 = parts.next()?.parse().ok()?;
+        for part in parts {
+            let part: i128 = part.parse().ok().filter(|part| *part < 60)?;
+            total = total.checked_mul(60)?.checked_add(part)?;
+        }
+        total
+    } else if digits.bytes().all(|b| b.is_ascii_digit()) {
+        digits.parse().ok()?
+    } else {
+        return None;
+    };
+    Some(sign * magnitude)
+}
+
+fn parse_float(raw: &str) -> Option<f64> {
+    let (sign, body) = split_sign(raw);
+    match body {
+        ".inf" | ".Inf" | ".INF" => return Some(sign as f64 * f64::INFINITY),
+        _ => {}
+    }
+    if matches!(raw, ".nan" | ".NaN" | ".NAN") {
+        return Some(f64::NAN);
+    }
+
+    let digits: String = body.chars().filter(|c| *c != '_').collect();
+    let numeric = digits
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-'));
+    if !numeric || !digits.bytes().any(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse::<f64>().ok().map(|float| sign as f64 * float)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canonical, within};
+
+    #[test]
+    fn canonical_spellings() {
+        for (raw, expected) in [
+            ("~", "null"),
+            ("Yes", "true"),
+            ("off", "false"),
+            ("0x1F", "31"),
+            ("1.0", "1"),
+            ("-0.0", "0"),
+            ("1e3", "1000"),
+            ("0.50", "0.5"),
+            (".NaN", ".nan"),
+            ("-.Inf", "-.inf"),
+            ("web", "web"),
+        ] {
+            assert_eq!(canonical(raw), expected, "{raw}");
+        }
+    }
+
+    #[test]
+    fn within_needs_a_float() {
+        assert!(within("1", "1.5", 0.5));
+        assert!(within("0.5", "0.75", 0.25));
+        assert!(!within("1", "2", 1.0));
+        assert!(!within("0.5", "0.76", 0.25));
+    }
+
+    #[test]
+    fn nan_is_never_within_reach() {
+        assert!(!within(".nan", ".nan", f64::INFINITY));
+        assert!(!within("1.0", ".nan", 1e9));
+        assert!(!within("web", "1.0", 1e9));
+    }
+}
@@ -0,0 +1,187 @@
+//! Extension points for how paths and values are matched up before they are
+//! compared. The built-in options that transform keys or values
+//! ([`case_insensitive_keys`](crate::PointlessPointer::case_insensitive_keys),
+//! [`expand_env`](crate::PointlessPointer::expand_env),
+//! [`equivalent_values`](crate::PointlessPointer::equivalent_values)) are
+//! normalizers too, and run before any given to the analyzer. Comparing
+//! Kubernetes quantities and scalars by their YAML type run after them, as
+//! long as [`strict_strings`](crate::PointlessPointer::strict_strings) is
+//! off.
+//!
+//! ```
+//! use pointless_pointer::{PointlessPointer, ValueNormalizer};
+//! use std::borrow::Cow;
+//!
+//! /// URLs with and without a trailing slash are the same.
+//! struct TrailingSlash;
+//!
+//! impl ValueNormalizer for TrailingSlash {
+//!     fn normalize_value<'a>(&self, _path: &[String], value: &'a str) -> Cow<'a, str> {
+//!         match value.strip_suffix('/') {
+//!             Some(trimmed) if value.contains("://") => Cow::Borrowed(trimmed),
+//!             _ => Cow::Borrowed(value),
+//!         }
+//!     }
+//! }
+//!
+//! let analyzer = PointlessPointer::from_sources(vec![
+//!     ("base".into(), "url: https://example.com/\n".into()),
+//!     ("prod".into(), "url: https://example.com\n".into()),
+//! ])
+//! .value_normalizer(TrailingSlash);
+//! let (pointless, _) = analyzer.analyze().unwrap();
+//! assert_eq!(pointless.len(), 1);
+//! ```
+
+use crate::{env, normalize, quantity};
+use glob::Pattern;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+/// Rewrites key segments, so that segments with the same form are the same
+/// key.
+pub trait KeyNormalizer: Send + Sync {
+    fn normalize_key<'a>(&self, segment: &'a str) -> Cow<'a, str>;
+}
+
+/// Rewrites scalar values, so that values with the same form compare equal.
+/// The YAML-aware comparison (`1.0` equals `1`, `yes` equals `true`) still
+/// applies to the result, and findings show the values as written.
+pub trait ValueNormalizer: Send + Sync {
+    /// `path` is where the value sits; lists of scalars are compared as a
+    /// whole and aren't passed through.
+    fn normalize_value<'a>(&self, path: &[String], value: &'a str) -> Cow<'a, str>;
+}
+
+/// Normalizers applied one after another, in order. An empty chain is the
+/// identity.
+pub struct Chain<'a, N: ?Sized>(Vec<&'a N>);
+
+impl<'a, N: ?Sized> Chain<'a, N> {
+    pub fn new(normalizers: Vec<&'a N>) -> Self {
+        Self(normalizers)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl KeyNormalizer for Chain<'_, dyn KeyNormalizer + '_> {
+    fn normalize_key<'a>(&self, segment: &'a str) -> Cow<'a, str> {
+        self.0.iter().fold(
+            Cow::Borrowed(segment),
+            |current, normalizer| match current {
+                Cow::Borrowed(text) => normalizer.normalize_key(text),
+                Cow::Owned(text) => Cow::Owned(normalizer.normalize_key(&text).into_owned()),
+            },
+        )
+    }
+}
+
+impl ValueNormalizer for Chain<'_, dyn ValueNormalizer + '_> {
+    fn normalize_value<'a>(&self, path: &[String], value: &'a str) -> Cow<'a, str> {
+        self.0
+            .iter()
+            .fold(Cow::Borrowed(value), |current, normalizer| match current {
+                Cow::Borrowed(text) => normalizer.normalize_value(path, text),
+                Cow::Owned(text) => {
+                    Cow::Owned(normalizer.normalize_value(path, &text).into_owned())
+                }
+            })
+    }
+}
+
+/// Keys regardless of case.
+pub(crate) struct LowercaseKeys;
+
+impl KeyNormalizer for LowercaseKeys {
+    fn normalize_key<'a>(&self, segment: &'a str) -> Cow<'a, str> {
+        Cow::Owned(segment.to_lowercase())
+    }
+}
+
+/// `${VAR}` placeholders expanded from the process environment. Unset
+/// variables are left as written and collected for a warning.
+#[derive(Default)]
+pub(crate) struct ExpandEnv {
+    pub(crate) unset: Mutex<BTreeSet<String>>,
+}
+
+impl ValueNormalizer for ExpandEnv {
+    fn normalize_value<'a>(&self, _path: &[String], value: &'a str) -> Cow<'a, str> {
+        let mut unset = self.unset.lock().unwrap_or_else(|error| error.into_inner());
+        match env::expand(value, &mut unset) {
+            Some(expanded) => Cow::Owned(expanded),
+            None => Cow::Borrowed(value),
+        }
+    }
+}
+
+/// Values of one class, at paths matching its glob, rewritten to the class's
+/// first value so that they all compare equal.
+pub(crate) struct Equivalences<'a>(pub(crate) &'a [(Pattern, Vec<String>)]);
+
+impl ValueNormalizer for Equivalences<'_> {
+    fn normalize_value<'a>(&self, path: &[String], value: &'a str) -> Cow<'a, str> {
+        let joined = path.join(".");
+        self.0
+            .iter()
+            .find(|(pattern, class)| {
+                class.iter().any(|member| member == value) && pattern.matches(&joined)
+            })
+            .map_or(Cow::Borrowed(value), |(_, class)| {
+                Cow::Owned(class[0].clone())
+            })
+    }
+}
+
+/// Kubernetes quantities at paths matching any of the globs, rewritten to
+/// their amount in billionths, so `500m` and `0.5` compare equal.
+pub(crate) struct Quantities<'a>(pub(crate) &'a [Pattern]);
+
+impl ValueNormalizer for Quantities<'_> {
+    fn normalize_value<'a>(&self, path: &[String], value: &'a str) -> Cow<'a, str> {
+        let joined = path.join(".");
+        if !self.0.iter().any(|pattern| pattern.matches(&joined)) {
+            return Cow::Borrowed(value);
+        }
+        quantity::parse(value).map_or(Cow::Borrowed(value), |amount| {
+            Cow::Owned(amount.to_string())
+        })
+    }
+}
+
+/// Scalars rewritten to one spelling of what YAML reads them as, so `1.0`
+/// equals `1` and `yes` equals `true`.
+pub(crate) struct YamlScalars;
+
+impl ValueNormalizer for YamlScalars {
+    fn normalize_value<'a>(&self, _path: &[String], value: &'a str) -> Cow<'a, str> {
+        normalize::canonical(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_applies_normalizers_in_order() {
+        let patterns = [Pattern::new("resources.*").unwrap()];
+        let quantities = Quantities(&patterns);
+        let chain: Chain<dyn ValueNormalizer> = Chain::new(vec![&quantities, &YamlScalars]);
+        let path = |joined: &str| joined.split('.').map(String::from).collect::<Vec<_>>();
+        assert_eq!(
+            chain.normalize_value(&path("resources.cpu"), "500m"),
+            "500000000"
+        );
+        assert_eq!(
+            chain.normalize_value(&path("resources.cpu"), "0.5"),
+            "500000000"
+        );
+        assert_eq!(chain.normalize_value(&path("replicas"), "2.0"), "2");
+        assert_eq!(chain.normalize_value(&path("name"), "500m"), "500m");
+    }
+}
@@ -0,0 +1,148 @@
+//! The order of values files declared by deployment tooling, such as an Argo
+//! CD application's `valueFiles:` list, which `--order-from` applies to the
+//! override files.
+
+use anyhow::{Context, Result, bail};
+use colored::Colorize;
+use pointless_pointer::is_url;
+use serde_norway::Value;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Values files in deployment order, lowest precedence first.
+pub struct ValueFileOrder {
+    manifest: PathBuf,
+    entries: Vec<String>,
+}
+
+impl ValueFileOrder {
+    /// Read the list from `path`: either a YAML list of file names, or a
+    /// document with a `valueFiles` list somewhere inside it.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let document: Value = serde_norway::from_str(&content)
+            .with_context(|| format!("Invalid YAML in {}", path.display()))?;
+        let Some(list) = file_list(&document) else {
+            bail!(
+                "{} is neither a list of files nor has a `valueFiles` list",
+                path.display()
+            );
+        };
+        let entries = list
+            .iter()
+            .map(|entry| match entry {
+                Value::String(entry) => Ok(entry.clone()),
+                other => bail!(
+                    "{} lists `{}`, which is not a file name",
+                    path.display(),
+                    serde_norway::to_string(other).unwrap_or_default().trim()
+                ),
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            manifest: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// `files` sorted by where the list has them. Files it doesn't list are
+    /// an error, unless `allow_extra` keeps them, with a warning, after the
+    /// listed ones in their given order. The manifest itself, which `--dir`
+    /// may pick up, is left out.
+    pub fn apply(&self, files: Vec<PathBuf>, allow_extra: bool) -> Result<Vec<PathBuf>> {
+        let manifest = self.manifest.canonicalize().ok();
+        let mut listed = Vec::new();
+        let mut extra = Vec::new();
+        for file in files {
+            if manifest.is_some() && file.canonicalize().ok() == manifest {
+                continue;
+            }
+            match self.position(&file) {
+                Some(position) => listed.push((position, file)),
+                None => extra.push(file),
+            }
+        }
+        if !extra.is_empty() {
+            let names: Vec<String> = extra
+                .iter()
+                .map(|file| file.display().to_string())
+                .collect();
+            if !allow_extra {
+                bail!(
+                    "Not listed in {}: {} (pass --allow-extra to keep them last)",
+                    self.manifest.display(),
+                    names.join(", ")
+                );
+            }
+            eprintln!(
+                "{} not listed in {}, so applied last: {}",
+                "warning:".yellow().bold(),
+                self.manifest.display(),
+                names.join(", ")
+            );
+        }
+        listed.sort_by_key(|(position, _)| *position);
+        Ok(listed
+            .into_iter()
+            .map(|(_, file)| file)
+            .chain(extra)
+            .collect())
+    }
+
+    /// Where `file` is in the list. An entry names it when both resolve to
+    /// the same file, the entry taken relative to the manifest, or when the
+    /// entry's path is a tail of the file's, as `envs/prod.yaml` is of
+    /// `charts/app/envs/prod.yaml`; the longest such tail wins.
+    fn position(&self, file: &Path) -> Option<usize> {
+        if is_url(file) {
+            let url = file.to_string_lossy();
+            return self.entries.iter().position(|entry| *entry == url);
+        }
+        let dir = self.manifest.parent().unwrap_or(Path::new(""));
+        let canonical = file.canonicalize().ok();
+        if let Some(position) = self.entries.iter().position(|entry| {
+            canonical.is_some() && dir.join(entry).canonicalize().ok() == canonical
+        }) {
+            return Some(position);
+        }
+        let file = normal_components(file);
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| (position, normal_components(Path::new(entry))))
+            .filter(|(_, entry)| !entry.is_empty() && file.ends_with(entry))
+            .max_by_key(|(_, entry)| entry.len())
+            .map(|(position, _)| position)
+    }
+}
+
+/// The list of files in `document`: the document itself when it is a list,
+/// or else the first `valueFiles` list at any depth.
+fn file_list(document: &Value) -> Option<&Vec<Value>> {
+    match document {
+        Value::Sequence(list) => Some(list),
+        _ => value_files(document),
+    }
+}
+
+fn value_files(node: &Value) -> Option<&Vec<Value>> {
+    match node {
+        Value::Mapping(mapping) => mapping.iter().find_map(|(key, value)| match (key, value) {
+            (Value::String(key), Value::Sequence(list)) if key == "valueFiles" => Some(list),
+            _ => value_files(value),
+        }),
+        Value::Sequence(list) => list.iter().find_map(value_files),
+        _ => None,
+    }
+}
+
+/// The names a path goes through, without `.` and roots.
+fn normal_components(path: &Path) -> Vec<&std::ffi::OsStr> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name),
+            _ => None,
+        })
+        .collect()
+}
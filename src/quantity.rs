@@ -0,0 +1,84 @@
+//! Kubernetes resource quantities (`500m`, `0.5`, `512Mi`, `1e3`), so equal
+//! amounts written differently compare equal.
+
+/// Billionths of a unit: `n`, the smallest suffix, is then a whole number.
+const SCALE: i128 = 1_000_000_000;
+
+/// The quantity's value in billionths, or `None` if `raw` isn't a quantity or
+/// is more precise than `1n`.
+pub(crate) fn parse(raw: &str) -> Option<i128> {
+    let raw = raw.trim();
+    let number_end = raw
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '+' | '-')))
+        .unwrap_or(raw.len());
+    let (number, suffix) = raw.split_at(number_end);
+    let scaled = parse_number(number)?;
+
+    let value = match suffix {
+        "" => scaled,
+        "n" => divide(scaled, SCALE)?,
+        "u" => divide(scaled, 1_000_000)?,
+        "m" => divide(scaled, 1_000)?,
+        "k" => scaled.checked_mul(1_000)?,
+        "M" => scaled.checked_mul(1_000_000)?,
+        "G" => scaled.checked_mul(1_000_000_000)?,
+        "T" => scaled.checked_mul(1_000_000_000_000)?,
+        "P" => scaled.checked_mul(1_000_000_000_000_000)?,
+        "E" => scaled.checked_mul(1_000_000_000_000_000_000)?,
+        "Ki" => scaled.checked_mul(1 << 10)?,
+        "Mi" => scaled.checked_mul(1 << 20)?,
+        "Gi" => scaled.checked_mul(1 << 30)?,
+        "Ti" => scaled.checked_mul(1 << 40)?,
+        "Pi" => scaled.checked_mul(1 << 50)?,
+        "Ei" => scaled.checked_mul(1 << 60)?,
+        _ => {
+            // Decimal exponent, e.g. `1e3` or `5E-1`
+            let exponent: i32 = suffix.strip_prefix(['e', 'E'])?.parse().ok()?;
+            if exponent >= 0 {
+                scaled.checked_mul(10i128.checked_pow(exponent.unsigned_abs())?)?
+            } else {
+                divide(scaled, 10i128.checked_pow(exponent.unsigned_abs())?)?
+            }
+        }
+    };
+    Some(value)
+}
+
+/// A signed decimal number, multiplied by [`SCALE`].
+fn parse_number(number: &str) -> Option<i128> {
+    let (sign, digits) = match number.as_bytes().first()? {
+        b'-' => (-1, &number[1..]),
+        b'+' => (1, &number[1..]),
+        _ => (1, number),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !(whole.bytes().all(|b| b.is_ascii_digit()) && fraction.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    // Trailing zeros don't add precision
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > 9 {
+        return None;
+    }
+
+    let whole: i128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let fraction: i128 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<i128>().ok()? * 10i128.pow(9 - fraction.len() as u32)
+    };
+    Some(sign * whole.checked_mul(SCALE)?.checked_add(fraction)?)
+}
+
+/// Exact division only; a remainder means the value is finer than `1n`.
+fn divide(value: i128, divisor: i128) -> Option<i128> {
+    (value % divisor == 0).then_some(value / divisor)
+}
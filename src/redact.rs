@@ -0,0 +1,149 @@
+//! Masking values in findings, so secrets don't end up in logs.
+
+use crate::{DuplicateKeyWarning, FileValues, Finding, Override};
+use anyhow::{Context, Result};
+use glob::Pattern;
+
+/// Which values to mask: those at paths matching any of the dotted-path
+/// globs, or every value when there are none.
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    patterns: Vec<Pattern>,
+}
+
+impl Redaction {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).with_context(|| format!("Invalid redact pattern: {pattern}"))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { patterns })
+    }
+
+    pub fn is_redacted(&self, path: &[String]) -> bool {
+        let joined = path.join(".");
+        self.patterns.is_empty() || self.patterns.iter().any(|pattern| pattern.matches(&joined))
+    }
+
+    /// Mask the values of findings in place, keeping their paths and
+    /// locations.
+    pub fn apply(&self, findings: &mut [impl Redact]) {
+        for finding in findings {
+            finding.redact(self);
+        }
+    }
+
+    /// Mask the values of layers listed by
+    /// [`PointlessPointer::collect`](crate::PointlessPointer::collect).
+    pub fn apply_to_values(&self, layers: &mut [FileValues]) {
+        for (path, value, _) in layers.iter_mut().flat_map(|layer| &mut layer.values) {
+            if self.is_redacted(path) {
+                mask(value);
+            }
+        }
+    }
+}
+
+/// Findings that carry values from the layers.
+pub trait Redact {
+    /// Mask the values of this finding when its path is redacted.
+    fn redact(&mut self, redaction: &Redaction);
+}
+
+impl Redact for Override {
+    fn redact(&mut self, redaction: &Redaction) {
+        if redaction.is_redacted(&self.path) {
+            mask(&mut self.value);
+            mask(&mut self.previous_value);
+            self.chain.iter_mut().for_each(|link| mask(&mut link.value));
+        }
+    }
+}
+
+impl Redact for DuplicateKeyWarning {
+    fn redact(&mut self, redaction: &Redaction) {
+        if redaction.is_redacted(&self.path) {
+            mask(&mut self.first_value);
+            mask(&mut self.second_value);
+        }
+    }
+}
+
+/// Every kind of finding is listed, so a new one has to say whether it
+/// carries values.
+impl Redact for Finding {
+    fn redact(&mut self, redaction: &Redaction) {
+        match self {
+            Finding::PointlessOverride(item) => item.redact(redaction),
+            Finding::DuplicateKey(warning) => warning.redact(redaction),
+            Finding::EffectiveOverride(item) => {
+                if redaction.is_redacted(&item.path) {
+                    mask(&mut item.value);
+                    mask(&mut item.previous_value);
+                    item.chain.iter_mut().for_each(|link| mask(&mut link.value));
+                }
+            }
+            Finding::TypeMismatch(warning) => {
+                if redaction.is_redacted(&warning.path) {
+                    warning.value.iter_mut().for_each(mask);
+                    warning.previous_value.iter_mut().for_each(mask);
+                }
+            }
+            Finding::NullEmpty(warning) => {
+                if redaction.is_redacted(&warning.path) {
+                    mask(&mut warning.value);
+                    mask(&mut warning.previous_value);
+                }
+            }
+            Finding::Conflict(warning) => {
+                if redaction.is_redacted(&warning.path) {
+                    mask(&mut warning.value);
+                    mask(&mut warning.previous_value);
+                }
+            }
+            Finding::DeadKey(dead) => {
+                if redaction.is_redacted(&dead.path) {
+                    mask(&mut dead.value);
+                }
+            }
+            Finding::DeepPath(_)
+            | Finding::RedundantFile(_)
+            | Finding::RedundantSubtree(_)
+            | Finding::EmptyFile(_)
+            | Finding::FileStats(_)
+            | Finding::Provenance(_)
+            | Finding::SkippedFile(_) => {}
+        }
+    }
+}
+
+/// One `*` per character, so the length still shows.
+fn mask(value: &mut String) {
+    *value = "*".repeat(value.chars().count());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DeadKey;
+
+    #[test]
+    fn masks_dead_keys() {
+        let mut findings = [Finding::DeadKey(DeadKey {
+            file: "values.yaml".to_string(),
+            path: vec!["db".to_string(), "password".to_string()],
+            value: "hunter2".to_string(),
+            line: 2,
+            column: 13,
+        })];
+        Redaction::new(&["*.password".to_string()])
+            .unwrap()
+            .apply(&mut findings);
+        let [Finding::DeadKey(dead)] = &findings else {
+            unreachable!();
+        };
+        assert_eq!(dead.value, "*******");
+    }
+}
@@ -0,0 +1,33 @@
+//! Layers fetched over HTTP(S) instead of read from disk.
+
+use crate::AnalyzeError;
+use std::io::Read;
+use std::time::Duration;
+
+/// Start fetching `url`, failing on anything but a successful response.
+/// `timeout` bounds the whole request, body included.
+pub(crate) fn open(
+    url: &str,
+    timeout: Option<Duration>,
+) -> Result<impl Read + use<>, AnalyzeError> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(timeout)
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let response = agent
+        .get(url)
+        .call()
+        .map_err(|source| AnalyzeError::Fetch {
+            url: url.to_string(),
+            source,
+        })?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AnalyzeError::HttpStatus {
+            url: url.to_string(),
+            status: status.as_u16(),
+        });
+    }
+    Ok(response.into_body().into_reader())
+}
@@ -0,0 +1,430 @@
+use crate::{
+    ConflictWarning, DeadKey, DeepPathWarning, DuplicateKeyWarning, EffectiveOverride, EmptyFile,
+    FileStats, FileValues, NullEmptyWarning, Override, PathProvenance, RedundantFile,
+    RedundantSubtree, SkippedFile, TypeMismatchWarning, ValueType, canonical_path, format_path,
+};
+use anyhow::Result;
+use serde::Serialize;
+use serde::ser::{SerializeSeq, Serializer};
+
+mod csv;
+mod github;
+mod html;
+mod junit;
+mod markdown;
+mod sarif;
+
+pub use self::csv::to_csv;
+pub use github::to_github;
+pub use html::to_html;
+pub use junit::to_junit;
+pub use markdown::to_markdown;
+pub use sarif::to_sarif;
+
+/// The findings the formats other than JSON and YAML list, each kind in the
+/// order given. Every kind here can fail a run, so every format shows all of
+/// them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Findings<'a> {
+    pub pointless_overrides: &'a [Override],
+    pub warnings: &'a [DuplicateKeyWarning],
+    pub deep_paths: &'a [DeepPathWarning],
+    pub type_mismatches: &'a [TypeMismatchWarning],
+    pub null_empty: &'a [NullEmptyWarning],
+    pub conflicts: &'a [ConflictWarning],
+    /// Subtrees shown in place of the pointless overrides under them, which
+    /// are left out of `pointless_overrides`
+    pub redundant_subtrees: &'a [RedundantSubtree],
+}
+
+/// A one-line description of the warning, for the formats that give each
+/// finding a message.
+fn deep_path_message(warning: &DeepPathWarning) -> String {
+    format!(
+        "`{}` is nested {} levels deep, beyond the limit of {}",
+        format_path(&warning.path),
+        warning.path.len(),
+        warning.max_depth
+    )
+}
+
+/// See [`deep_path_message`].
+fn type_mismatch_message(warning: &TypeMismatchWarning) -> String {
+    format!(
+        "`{}` is {}, where {}:{} has {}",
+        format_path(&warning.path),
+        describe_type(warning.value_type, warning.value.as_deref()),
+        warning.previous_file,
+        warning.previous_line,
+        describe_type(warning.previous_type, warning.previous_value.as_deref())
+    )
+}
+
+/// A type, followed by the value when it's a scalar.
+fn describe_type(value_type: ValueType, value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("{value_type} `{value}`"),
+        None => format!("a {value_type}"),
+    }
+}
+
+/// See [`deep_path_message`].
+fn null_empty_message(warning: &NullEmptyWarning) -> String {
+    let (now, before) = if warning.is_null {
+        ("null", "an empty string")
+    } else {
+        ("an empty string", "null")
+    };
+    format!(
+        "`{}` is {now}, where {}:{} has {before}",
+        format_path(&warning.path),
+        warning.previous_file,
+        warning.previous_line
+    )
+}
+
+/// See [`deep_path_message`].
+fn conflict_message(warning: &ConflictWarning) -> String {
+    format!(
+        "`{}` is set to `{}`, but {}:{} sets it to `{}`, so the order of the files decides",
+        format_path(&warning.path),
+        warning.value,
+        warning.previous_file,
+        warning.previous_line,
+        warning.previous_value
+    )
+}
+
+/// See [`deep_path_message`].
+fn redundant_subtree_message(subtree: &RedundantSubtree) -> String {
+    format!(
+        "All {} value(s) under `{}` are already in effect, so the whole subtree can go",
+        subtree.values,
+        format_path(&subtree.path)
+    )
+}
+
+/// Every layer's values, as listed by [`PointlessPointer::collect`], as JSON.
+///
+/// [`PointlessPointer::collect`]: crate::PointlessPointer::collect
+pub fn values_to_json(layers: &[FileValues]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(layers)?)
+}
+
+/// See [`values_to_json`].
+pub fn values_to_yaml(layers: &[FileValues]) -> Result<String> {
+    Ok(serde_norway::to_string(layers)?)
+}
+
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    pub pointless_overrides: usize,
+    pub warnings: usize,
+    /// Findings dropped by ignore patterns or the baseline
+    pub suppressed: usize,
+    /// Only present when effective overrides were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_overrides: Option<usize>,
+    /// Only present when a maximum depth was set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deep_paths: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_mismatches: Option<usize>,
+    /// Only present when null-versus-empty warnings were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub null_empty: Option<usize>,
+    /// Only present when conflict warnings were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflicts: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redundant_files: Option<usize>,
+    /// Only present when subtrees are rolled up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redundant_subtrees: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub empty_files: Option<usize>,
+    /// Per-file path counts; only present when stats were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_stats: Option<Vec<FileStats>>,
+    /// Only present when unreadable files are skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_files: Option<usize>,
+    /// Only present when provenance was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<usize>,
+    /// Only present when dead keys were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dead_keys: Option<usize>,
+}
+
+/// Machine-readable report: findings plus a summary of their counts, as JSON
+/// or the same structure in YAML.
+///
+/// Every finding with a path also has its
+/// [`canonical_path`](crate::canonical_path), after its other fields.
+#[derive(Debug, Serialize)]
+pub struct JsonReport<'a> {
+    #[serde(serialize_with = "with_canonical_paths")]
+    pub pointless_overrides: &'a [Override],
+    #[serde(serialize_with = "with_canonical_paths")]
+    pub warnings: &'a [DuplicateKeyWarning],
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "some_with_canonical_paths"
+    )]
+    pub effective_overrides: Option<&'a [EffectiveOverride]>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "some_with_canonical_paths"
+    )]
+    pub deep_paths: Option<&'a [DeepPathWarning]>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "some_with_canonical_paths"
+    )]
+    pub type_mismatches: Option<&'a [TypeMismatchWarning]>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "some_with_canonical_paths"
+    )]
+    pub null_empty: Option<&'a [NullEmptyWarning]>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "some_with_canonical_paths"
+    )]
+    pub conflicts: Option<&'a [ConflictWarning]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redundant_files: Option<&'a [RedundantFile]>,
+    /// The overrides under these are left out of `pointless_overrides`
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "some_with_canonical_paths"
+    )]
+    pub redundant_subtrees: Option<&'a [RedundantSubtree]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub empty_files: Option<&'a [EmptyFile]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_files: Option<&'a [SkippedFile]>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "some_with_canonical_paths"
+    )]
+    pub provenance: Option<&'a [PathProvenance]>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "some_with_canonical_paths"
+    )]
+    pub dead_keys: Option<&'a [DeadKey]>,
+    pub summary: Summary,
+}
+
+/// A finding located at a path.
+trait HasPath {
+    fn path(&self) -> &[String];
+}
+
+macro_rules! has_path {
+    ($($finding:ty),*) => {
+        $(impl HasPath for $finding {
+            fn path(&self) -> &[String] {
+                &self.path
+            }
+        })*
+    };
+}
+
+has_path!(
+    Override,
+    DuplicateKeyWarning,
+    EffectiveOverride,
+    DeepPathWarning,
+    TypeMismatchWarning,
+    NullEmptyWarning,
+    ConflictWarning,
+    RedundantSubtree,
+    PathProvenance,
+    DeadKey
+);
+
+/// A finding serialized with its canonical path after its own fields.
+#[derive(Serialize)]
+struct WithCanonicalPath<'a, T> {
+    #[serde(flatten)]
+    finding: &'a T,
+    canonical_path: String,
+}
+
+fn with_canonical_paths<S, T>(findings: &&[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + HasPath,
+{
+    let mut seq = serializer.serialize_seq(Some(findings.len()))?;
+    for finding in findings.iter() {
+        seq.serialize_element(&WithCanonicalPath {
+            finding,
+            canonical_path: canonical_path(finding.path()),
+        })?;
+    }
+    seq.end()
+}
+
+/// [`with_canonical_paths`] for the sections that are only sometimes there.
+fn some_with_canonical_paths<S, T>(
+    findings: &Option<&[T]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + HasPath,
+{
+    match findings {
+        Some(findings) => with_canonical_paths(findings, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+impl<'a> JsonReport<'a> {
+    pub fn new(
+        pointless_overrides: &'a [Override],
+        warnings: &'a [DuplicateKeyWarning],
+        suppressed: usize,
+    ) -> Self {
+        Self {
+            pointless_overrides,
+            warnings,
+            effective_overrides: None,
+            deep_paths: None,
+            type_mismatches: None,
+            null_empty: None,
+            conflicts: None,
+            redundant_files: None,
+            redundant_subtrees: None,
+            empty_files: None,
+            skipped_files: None,
+            provenance: None,
+            dead_keys: None,
+            summary: Summary {
+                pointless_overrides: pointless_overrides.len(),
+                warnings: warnings.len(),
+                suppressed,
+                effective_overrides: None,
+                deep_paths: None,
+                type_mismatches: None,
+                null_empty: None,
+                conflicts: None,
+                redundant_files: None,
+                redundant_subtrees: None,
+                empty_files: None,
+                file_stats: None,
+                skipped_files: None,
+                provenance: None,
+                dead_keys: None,
+            },
+        }
+    }
+
+    /// Include the overrides that change a value.
+    pub fn with_effective_overrides(
+        mut self,
+        effective_overrides: &'a [EffectiveOverride],
+    ) -> Self {
+        self.effective_overrides = Some(effective_overrides);
+        self.summary.effective_overrides = Some(effective_overrides.len());
+        self
+    }
+
+    /// Include the values nested beyond the maximum depth.
+    pub fn with_deep_paths(mut self, deep_paths: &'a [DeepPathWarning]) -> Self {
+        self.deep_paths = Some(deep_paths);
+        self.summary.deep_paths = Some(deep_paths.len());
+        self
+    }
+
+    /// Include the paths whose type changes between layers.
+    pub fn with_type_mismatches(mut self, type_mismatches: &'a [TypeMismatchWarning]) -> Self {
+        self.type_mismatches = Some(type_mismatches);
+        self.summary.type_mismatches = Some(type_mismatches.len());
+        self
+    }
+
+    /// Include the nulls replaced by empty strings and the other way round.
+    pub fn with_null_empty(mut self, null_empty: &'a [NullEmptyWarning]) -> Self {
+        self.null_empty = Some(null_empty);
+        self.summary.null_empty = Some(null_empty.len());
+        self
+    }
+
+    /// Include the values override files disagree on.
+    pub fn with_conflicts(mut self, conflicts: &'a [ConflictWarning]) -> Self {
+        self.conflicts = Some(conflicts);
+        self.summary.conflicts = Some(conflicts.len());
+        self
+    }
+
+    /// Include the layers whose every value is a pointless override.
+    pub fn with_redundant_files(mut self, redundant_files: &'a [RedundantFile]) -> Self {
+        self.redundant_files = Some(redundant_files);
+        self.summary.redundant_files = Some(redundant_files.len());
+        self
+    }
+
+    /// Include the subtrees whose every value is a pointless override.
+    pub fn with_redundant_subtrees(mut self, redundant_subtrees: &'a [RedundantSubtree]) -> Self {
+        self.redundant_subtrees = Some(redundant_subtrees);
+        self.summary.redundant_subtrees = Some(redundant_subtrees.len());
+        self
+    }
+
+    /// Include the layers that set no values.
+    pub fn with_empty_files(mut self, empty_files: &'a [EmptyFile]) -> Self {
+        self.empty_files = Some(empty_files);
+        self.summary.empty_files = Some(empty_files.len());
+        self
+    }
+
+    /// Include how much of each layer is pointless, in the summary.
+    pub fn with_file_stats(mut self, file_stats: &[FileStats]) -> Self {
+        self.summary.file_stats = Some(file_stats.to_vec());
+        self
+    }
+
+    /// Include the files left out because they couldn't be read.
+    pub fn with_skipped_files(mut self, skipped_files: &'a [SkippedFile]) -> Self {
+        self.skipped_files = Some(skipped_files);
+        self.summary.skipped_files = Some(skipped_files.len());
+        self
+    }
+
+    /// Include the layer in effect for every path.
+    pub fn with_provenance(mut self, provenance: &'a [PathProvenance]) -> Self {
+        self.provenance = Some(provenance);
+        self.summary.provenance = Some(provenance.len());
+        self
+    }
+
+    /// Include the values of the lowest layer that no other layer touches.
+    pub fn with_dead_keys(mut self, dead_keys: &'a [DeadKey]) -> Self {
+        self.dead_keys = Some(dead_keys);
+        self.summary.dead_keys = Some(dead_keys.len());
+        self
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Just the counts, for when the findings themselves aren't wanted.
+    pub fn summary_to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.summary)?)
+    }
+
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_norway::to_string(self)?)
+    }
+
+    /// See [`summary_to_json`](Self::summary_to_json).
+    pub fn summary_to_yaml(&self) -> Result<String> {
+        Ok(serde_norway::to_string(&self.summary)?)
+    }
+}
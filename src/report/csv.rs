@@ -0,0 +1,125 @@
+//! CSV output for spreadsheets: one row per finding.
+
+use super::Findings;
+use crate::format_path;
+use anyhow::Result;
+
+const HEADER: [&str; 9] = [
+    "kind",
+    "severity",
+    "file",
+    "path",
+    "value",
+    "line",
+    "previous_file",
+    "previous_line",
+    "previous_value",
+];
+
+/// Duplicate keys fill the `previous_*` columns from the first occurrence in
+/// the same file. Redundant subtrees give how many values they hold as their
+/// `value`, deep paths leave it empty, and neither fills the `previous_*`
+/// columns. A type change to or from a collection gives the type instead of
+/// a value. The header row is written even without findings.
+pub fn to_csv(findings: &Findings) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(HEADER)?;
+    for override_item in findings.pointless_overrides {
+        writer.write_record([
+            "pointless_override",
+            &override_item.severity.to_string(),
+            &override_item.file,
+            &format_path(&override_item.path),
+            &override_item.value,
+            &override_item.line.to_string(),
+            &override_item.previous_file,
+            &override_item.previous_line.to_string(),
+            &override_item.previous_value,
+        ])?;
+    }
+    for subtree in findings.redundant_subtrees {
+        writer.write_record([
+            "redundant_subtree",
+            &subtree.severity.to_string(),
+            &subtree.file,
+            &format_path(&subtree.path),
+            &subtree.values.to_string(),
+            &subtree.line.to_string(),
+            "",
+            "",
+            "",
+        ])?;
+    }
+    for warning in findings.warnings {
+        writer.write_record([
+            "duplicate_key",
+            &warning.severity.to_string(),
+            &warning.file,
+            &format_path(&warning.path),
+            &warning.second_value,
+            &warning.second_line.to_string(),
+            &warning.file,
+            &warning.first_line.to_string(),
+            &warning.first_value,
+        ])?;
+    }
+    for warning in findings.deep_paths {
+        writer.write_record([
+            "deep_path",
+            &warning.severity.to_string(),
+            &warning.file,
+            &format_path(&warning.path),
+            "",
+            &warning.line.to_string(),
+            "",
+            "",
+            "",
+        ])?;
+    }
+    for warning in findings.type_mismatches {
+        writer.write_record([
+            "type_mismatch",
+            &warning.severity.to_string(),
+            &warning.file,
+            &format_path(&warning.path),
+            &warning
+                .value
+                .clone()
+                .unwrap_or_else(|| warning.value_type.to_string()),
+            &warning.line.to_string(),
+            &warning.previous_file,
+            &warning.previous_line.to_string(),
+            &warning
+                .previous_value
+                .clone()
+                .unwrap_or_else(|| warning.previous_type.to_string()),
+        ])?;
+    }
+    for warning in findings.null_empty {
+        writer.write_record([
+            "null_empty",
+            &warning.severity.to_string(),
+            &warning.file,
+            &format_path(&warning.path),
+            &warning.value,
+            &warning.line.to_string(),
+            &warning.previous_file,
+            &warning.previous_line.to_string(),
+            &warning.previous_value,
+        ])?;
+    }
+    for warning in findings.conflicts {
+        writer.write_record([
+            "conflict",
+            &warning.severity.to_string(),
+            &warning.file,
+            &format_path(&warning.path),
+            &warning.value,
+            &warning.line.to_string(),
+            &warning.previous_file,
+            &warning.previous_line.to_string(),
+            &warning.previous_value,
+        ])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
@@ -0,0 +1,140 @@
+//! GitHub Actions workflow commands, which show up as annotations on the
+//! pull request diff.
+
+use super::{
+    Findings, conflict_message, deep_path_message, null_empty_message, redundant_subtree_message,
+    type_mismatch_message,
+};
+use crate::{Severity, format_path};
+use std::fmt::Write;
+
+/// Escape the message part of a workflow command.
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property, where `:` and `,` are delimiters too.
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn annotation(
+    out: &mut String,
+    severity: Severity,
+    file: &str,
+    line: usize,
+    column: usize,
+    title: &str,
+    message: &str,
+) {
+    let command = match severity {
+        Severity::Info => "notice",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    };
+    let _ = writeln!(
+        out,
+        "::{command} file={},line={line},col={column},title={}::{}",
+        escape_property(file),
+        escape_property(title),
+        escape_data(message)
+    );
+}
+
+/// One `::notice`, `::warning` or `::error` command per finding, as its
+/// severity says, and nothing else.
+pub fn to_github(findings: &Findings) -> String {
+    let mut out = String::new();
+    for override_item in findings.pointless_overrides {
+        annotation(
+            &mut out,
+            override_item.severity,
+            &override_item.file,
+            override_item.line,
+            override_item.column,
+            "Pointless override",
+            &format!(
+                "{} is set to {}, the same as in {}:{}",
+                format_path(&override_item.path),
+                override_item.value,
+                override_item.previous_file,
+                override_item.previous_line
+            ),
+        );
+    }
+    for subtree in findings.redundant_subtrees {
+        annotation(
+            &mut out,
+            subtree.severity,
+            &subtree.file,
+            subtree.line,
+            subtree.column,
+            "Redundant subtree",
+            &redundant_subtree_message(subtree),
+        );
+    }
+    for warning in findings.warnings {
+        annotation(
+            &mut out,
+            warning.severity,
+            &warning.file,
+            warning.second_key_line,
+            warning.second_key_column,
+            "Duplicate key",
+            &format!(
+                "{} is set to {}, but line {} already set it to {}",
+                format_path(&warning.path),
+                warning.second_value,
+                warning.first_key_line,
+                warning.first_value
+            ),
+        );
+    }
+    for warning in findings.deep_paths {
+        annotation(
+            &mut out,
+            warning.severity,
+            &warning.file,
+            warning.line,
+            warning.column,
+            "Deep path",
+            &deep_path_message(warning),
+        );
+    }
+    for warning in findings.type_mismatches {
+        annotation(
+            &mut out,
+            warning.severity,
+            &warning.file,
+            warning.line,
+            warning.column,
+            "Type change",
+            &type_mismatch_message(warning),
+        );
+    }
+    for warning in findings.null_empty {
+        annotation(
+            &mut out,
+            warning.severity,
+            &warning.file,
+            warning.line,
+            warning.column,
+            "Null or empty string",
+            &null_empty_message(warning),
+        );
+    }
+    for warning in findings.conflicts {
+        annotation(
+            &mut out,
+            warning.severity,
+            &warning.file,
+            warning.line,
+            warning.column,
+            "Conflicting overrides",
+            &conflict_message(warning),
+        );
+    }
+    out
+}
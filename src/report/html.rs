@@ -0,0 +1,231 @@
+//! A self-contained HTML page, for sharing results outside the terminal.
+
+use super::Findings;
+use crate::{Severity, ValueType, format_path};
+use std::fmt::Write;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.4em; }
+.summary span { display: inline-block; margin-right: 1.5em; }
+details { border: 1px solid #ddd; border-radius: 4px; margin: 0.5em 0; padding: 0.3em 0.8em; }
+summary { cursor: pointer; font-weight: bold; }
+.clean { color: #2a7a2a; }
+.entry { border-left: 4px solid; margin: 0.6em 0; padding: 0.2em 0.8em; }
+.pointless { border-color: #c0392b; background: #fdf0ee; }
+.duplicate, .warning { border-color: #d68910; background: #fef6e7; }
+.kind { font-size: 0.8em; text-transform: uppercase; letter-spacing: 0.05em; }
+code { background: #f3f3f3; padding: 0 0.2em; }
+";
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A type, followed by the value when it's a scalar.
+fn describe(value_type: ValueType, value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("{value_type} <code>{}</code>", escape(value)),
+        None => format!("a {value_type}"),
+    }
+}
+
+/// A warning other than a duplicate key: its kind and severity, then what
+/// it says about the value.
+fn warning_entry(kind: &str, severity: Severity, text: &str) -> String {
+    format!(
+        "<div class=\"entry warning\"><div class=\"kind\">{kind} ({severity})</div>{text}</div>\n"
+    )
+}
+
+/// A header with the counts, then a collapsible section per file in `files`
+/// listing its findings in the order given. Sections with findings start
+/// open.
+pub fn to_html(files: &[String], findings: &Findings) -> String {
+    let mut sections = String::new();
+    for file in files {
+        let overrides: Vec<_> = findings
+            .pointless_overrides
+            .iter()
+            .filter(|item| &item.file == file)
+            .collect();
+        let file_warnings: Vec<_> = findings
+            .warnings
+            .iter()
+            .filter(|warning| &warning.file == file)
+            .collect();
+        let subtrees: Vec<_> = findings
+            .redundant_subtrees
+            .iter()
+            .filter(|subtree| &subtree.file == file)
+            .collect();
+        let mut other_warnings = Vec::new();
+        for warning in findings
+            .deep_paths
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            other_warnings.push(warning_entry(
+                "Deep path",
+                warning.severity,
+                &format!(
+                    "<code>{}</code> at line {} is nested {} levels deep, beyond the limit of {}",
+                    escape(&format_path(&warning.path)),
+                    warning.line,
+                    warning.path.len(),
+                    warning.max_depth
+                ),
+            ));
+        }
+        for warning in findings
+            .type_mismatches
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            other_warnings.push(warning_entry(
+                "Type change",
+                warning.severity,
+                &format!(
+                    "<code>{}</code> is {} at line {}, where {}:{} has {}",
+                    escape(&format_path(&warning.path)),
+                    describe(warning.value_type, warning.value.as_deref()),
+                    warning.line,
+                    escape(&warning.previous_file),
+                    warning.previous_line,
+                    describe(warning.previous_type, warning.previous_value.as_deref())
+                ),
+            ));
+        }
+        for warning in findings
+            .null_empty
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            let (now, before) = if warning.is_null {
+                ("null", "an empty string")
+            } else {
+                ("an empty string", "null")
+            };
+            other_warnings.push(warning_entry(
+                "Null or empty string",
+                warning.severity,
+                &format!(
+                    "<code>{}</code> is {now} at line {}, where {}:{} has {before}",
+                    escape(&format_path(&warning.path)),
+                    warning.line,
+                    escape(&warning.previous_file),
+                    warning.previous_line
+                ),
+            ));
+        }
+        for warning in findings
+            .conflicts
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            other_warnings.push(warning_entry(
+                "Conflict",
+                warning.severity,
+                &format!(
+                    "<code>{}</code> = <code>{}</code> at line {}, but {}:{} sets it to <code>{}</code>",
+                    escape(&format_path(&warning.path)),
+                    escape(&warning.value),
+                    warning.line,
+                    escape(&warning.previous_file),
+                    warning.previous_line,
+                    escape(&warning.previous_value)
+                ),
+            ));
+        }
+        let count = subtrees.len() + overrides.len() + file_warnings.len() + other_warnings.len();
+
+        let _ = writeln!(
+            sections,
+            "<details{}>\n<summary>{} ({count} finding(s))</summary>",
+            if count > 0 { " open" } else { "" },
+            escape(file)
+        );
+        if count == 0 {
+            sections.push_str("<p class=\"clean\">No findings.</p>\n");
+        }
+        for subtree in subtrees {
+            let _ = writeln!(
+                sections,
+                "<div class=\"entry pointless\"><div class=\"kind\">Redundant subtree ({})</div>\
+                 <code>{}</code> at line {}: all {} value(s) under it are already in effect</div>",
+                subtree.severity,
+                escape(&format_path(&subtree.path)),
+                subtree.line,
+                subtree.values
+            );
+        }
+        for override_item in overrides {
+            let _ = writeln!(
+                sections,
+                "<div class=\"entry pointless\"><div class=\"kind\">Pointless override ({})</div>\
+                 <code>{}</code> = <code>{}</code> at line {}, the same as {}:{}</div>",
+                override_item.severity,
+                escape(&format_path(&override_item.path)),
+                escape(&override_item.value),
+                override_item.line,
+                escape(&override_item.previous_file),
+                override_item.previous_line
+            );
+        }
+        for warning in file_warnings {
+            let _ = writeln!(
+                sections,
+                "<div class=\"entry duplicate\"><div class=\"kind\">Duplicate key ({})</div>\
+                 <code>{}</code> = <code>{}</code> at line {}, already set to <code>{}</code> at line {}</div>",
+                warning.severity,
+                escape(&format_path(&warning.path)),
+                escape(&warning.second_value),
+                warning.second_line,
+                escape(&warning.first_value),
+                warning.first_line
+            );
+        }
+        sections.extend(other_warnings);
+        sections.push_str("</details>\n");
+    }
+
+    // The other findings are only counted when there are any
+    let mut counts = String::new();
+    for (count, what) in [
+        (findings.redundant_subtrees.len(), "redundant subtree(s)"),
+        (findings.deep_paths.len(), "deeply nested value(s)"),
+        (findings.type_mismatches.len(), "type change(s)"),
+        (findings.null_empty.len(), "null/empty string swap(s)"),
+        (
+            findings.conflicts.len(),
+            "value(s) override files disagree on",
+        ),
+    ] {
+        if count > 0 {
+            let _ = write!(counts, "<span>{count} {what}</span>");
+        }
+    }
+
+    let name = env!("CARGO_PKG_NAME");
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{name} report</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{name} report</h1>\n<p class=\"summary\"><span>{} file(s)</span>\
+         <span>{} pointless override(s)</span><span>{} duplicate key warning(s)</span>{counts}</p>\n\
+         {sections}</body>\n</html>\n",
+        files.len(),
+        findings.pointless_overrides.len(),
+        findings.warnings.len()
+    )
+}
@@ -0,0 +1,213 @@
+//! JUnit XML output for test-result dashboards.
+
+use super::{
+    Findings, conflict_message, deep_path_message, null_empty_message, redundant_subtree_message,
+    type_mismatch_message,
+};
+use crate::format_path;
+use std::fmt::Write;
+
+/// A failing test case: name, failure type and message.
+type Failure = (String, &'static str, String);
+
+/// `text` as an attribute value. Line breaks and tabs are written as
+/// character references, since parsers otherwise normalize them to spaces.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// One `<testsuite>` per file in `files`, with a failing `<testcase>` per
+/// finding. Files without findings get a single passing test case.
+pub fn to_junit(files: &[String], findings: &Findings) -> String {
+    let mut suites = String::new();
+    let mut total_tests = 0;
+    let mut total_failures = 0;
+
+    for file in files {
+        let mut failures: Vec<Failure> = Vec::new();
+        for override_item in findings
+            .pointless_overrides
+            .iter()
+            .filter(|item| &item.file == file)
+        {
+            let path = format_path(&override_item.path);
+            failures.push((
+                format!("pointless override: {path}"),
+                "pointless-override",
+                format!(
+                    "{}: {}:{}: `{path}` is set to `{}`, the same as {}:{}",
+                    override_item.severity,
+                    override_item.file,
+                    override_item.line,
+                    override_item.value,
+                    override_item.previous_file,
+                    override_item.previous_line
+                ),
+            ));
+        }
+        for subtree in findings
+            .redundant_subtrees
+            .iter()
+            .filter(|subtree| &subtree.file == file)
+        {
+            failures.push((
+                format!("redundant subtree: {}", format_path(&subtree.path)),
+                "redundant-subtree",
+                format!(
+                    "{}: {}:{}: {}",
+                    subtree.severity,
+                    subtree.file,
+                    subtree.line,
+                    redundant_subtree_message(subtree)
+                ),
+            ));
+        }
+        for warning in findings
+            .warnings
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            let path = format_path(&warning.path);
+            failures.push((
+                format!("duplicate key: {path}"),
+                "duplicate-key",
+                format!(
+                    "{}: {}:{}: `{path}` is set to `{}`, but line {} already set it to `{}`",
+                    warning.severity,
+                    warning.file,
+                    warning.second_line,
+                    warning.second_value,
+                    warning.first_line,
+                    warning.first_value
+                ),
+            ));
+        }
+        for warning in findings
+            .deep_paths
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            failures.push((
+                format!("deep path: {}", format_path(&warning.path)),
+                "deep-path",
+                format!(
+                    "{}: {}:{}: {}",
+                    warning.severity,
+                    warning.file,
+                    warning.line,
+                    deep_path_message(warning)
+                ),
+            ));
+        }
+        for warning in findings
+            .type_mismatches
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            failures.push((
+                format!("type change: {}", format_path(&warning.path)),
+                "type-mismatch",
+                format!(
+                    "{}: {}:{}: {}",
+                    warning.severity,
+                    warning.file,
+                    warning.line,
+                    type_mismatch_message(warning)
+                ),
+            ));
+        }
+        for warning in findings
+            .null_empty
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            failures.push((
+                format!("null or empty string: {}", format_path(&warning.path)),
+                "null-empty",
+                format!(
+                    "{}: {}:{}: {}",
+                    warning.severity,
+                    warning.file,
+                    warning.line,
+                    null_empty_message(warning)
+                ),
+            ));
+        }
+        for warning in findings
+            .conflicts
+            .iter()
+            .filter(|warning| &warning.file == file)
+        {
+            failures.push((
+                format!("conflict: {}", format_path(&warning.path)),
+                "conflict",
+                format!(
+                    "{}: {}:{}: {}",
+                    warning.severity,
+                    warning.file,
+                    warning.line,
+                    conflict_message(warning)
+                ),
+            ));
+        }
+
+        let tests = failures.len().max(1);
+        total_tests += tests;
+        total_failures += failures.len();
+        let _ = writeln!(
+            suites,
+            "  <testsuite name=\"{}\" tests=\"{tests}\" failures=\"{}\">",
+            escape(file),
+            failures.len()
+        );
+        if failures.is_empty() {
+            let _ = writeln!(
+                suites,
+                "    <testcase name=\"no findings\" classname=\"{}\"/>",
+                escape(file)
+            );
+        }
+        for (name, kind, message) in &failures {
+            let _ = writeln!(
+                suites,
+                "    <testcase name=\"{}\" classname=\"{}\">\n      <failure type=\"{kind}\" message=\"{}\"/>\n    </testcase>",
+                escape(name),
+                escape(file),
+                escape(message)
+            );
+        }
+        suites.push_str("  </testsuite>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"{}\" tests=\"{total_tests}\" failures=\"{total_failures}\">\n{suites}</testsuites>",
+        env!("CARGO_PKG_NAME")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape;
+
+    #[test]
+    fn escapes_line_breaks_in_attributes() {
+        assert_eq!(
+            escape("a: |\n  <x>\r\n\t\"y\""),
+            "a: |&#10;  &lt;x&gt;&#13;&#10;&#9;&quot;y&quot;"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! GitHub-flavored Markdown output for pull request comments.
+
+use super::Findings;
+use crate::{STDIN_LABEL, ValueType, format_path};
+use std::fmt::Write;
+
+/// A `file:line` reference, linked to `{repo_url}/{file}#L{line}` when a
+/// repository URL is given.
+fn location(file: &str, line: usize, repo_url: Option<&str>) -> String {
+    let text = cell(&format!("{file}:{line}"));
+    match repo_url {
+        Some(url) if file != STDIN_LABEL => {
+            let file = file.strip_prefix("./").unwrap_or(file);
+            format!("[{text}]({}/{file}#L{line})", url.trim_end_matches('/'))
+        }
+        _ => text,
+    }
+}
+
+/// Text that is safe inside a table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// An inline code span, widening the fence if the text contains backticks.
+fn code(text: &str) -> String {
+    let text = cell(text);
+    if text.contains('`') {
+        format!("`` {text} ``")
+    } else {
+        format!("`{text}`")
+    }
+}
+
+/// A type, followed by the value when it's a scalar.
+fn describe(value_type: ValueType, value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("{value_type} {}", code(value)),
+        None => value_type.to_string(),
+    }
+}
+
+/// A heading with the counts, then one table per kind of finding. Subtrees
+/// and warnings other than duplicate keys are only counted in the heading
+/// when there are any.
+pub fn to_markdown(findings: &Findings, repo_url: Option<&str>) -> String {
+    let Findings {
+        pointless_overrides,
+        warnings,
+        deep_paths,
+        type_mismatches,
+        null_empty,
+        conflicts,
+        redundant_subtrees,
+    } = *findings;
+    let mut counts = vec![
+        format!("{} pointless override(s)", pointless_overrides.len()),
+        format!("{} duplicate key warning(s)", warnings.len()),
+    ];
+    for (count, what) in [
+        (redundant_subtrees.len(), "redundant subtree(s)"),
+        (deep_paths.len(), "deeply nested value(s)"),
+        (type_mismatches.len(), "type change(s)"),
+        (null_empty.len(), "null/empty string swap(s)"),
+        (conflicts.len(), "value(s) override files disagree on"),
+    ] {
+        if count > 0 {
+            counts.push(format!("{count} {what}"));
+        }
+    }
+    let mut out = format!("## {}: {}\n\n", env!("CARGO_PKG_NAME"), counts.join(", "));
+
+    if pointless_overrides.is_empty() {
+        if redundant_subtrees.is_empty() {
+            out.push_str("No pointless overrides found.\n");
+        }
+    } else {
+        out.push_str("### Pointless overrides\n\n");
+        out.push_str(
+            "| Severity | File | Path | Value | Same as |\n| --- | --- | --- | --- | --- |\n",
+        );
+        for override_item in pointless_overrides {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                override_item.severity,
+                location(&override_item.file, override_item.line, repo_url),
+                code(&format_path(&override_item.path)),
+                code(&override_item.value),
+                location(
+                    &override_item.previous_file,
+                    override_item.previous_line,
+                    repo_url
+                )
+            );
+        }
+    }
+
+    if !redundant_subtrees.is_empty() {
+        if !pointless_overrides.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("### Redundant subtrees\n\n");
+        out.push_str("| Severity | File | Path | Values |\n| --- | --- | --- | --- |\n");
+        for subtree in redundant_subtrees {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                subtree.severity,
+                location(&subtree.file, subtree.line, repo_url),
+                code(&format_path(&subtree.path)),
+                subtree.values
+            );
+        }
+    }
+
+    if !warnings.is_empty() {
+        out.push_str("\n### Duplicate keys with different values\n\n");
+        out.push_str(
+            "| Severity | File | Path | First value | Second value |\n| --- | --- | --- | --- | --- |\n",
+        );
+        for warning in warnings {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} (line {}) | {} |",
+                warning.severity,
+                location(&warning.file, warning.second_line, repo_url),
+                code(&format_path(&warning.path)),
+                code(&warning.first_value),
+                warning.first_line,
+                code(&warning.second_value)
+            );
+        }
+    }
+
+    if !deep_paths.is_empty() {
+        out.push_str("\n### Values nested too deeply\n\n");
+        out.push_str("| Severity | File | Path | Depth |\n| --- | --- | --- | --- |\n");
+        for warning in deep_paths {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} (limit {}) |",
+                warning.severity,
+                location(&warning.file, warning.line, repo_url),
+                code(&format_path(&warning.path)),
+                warning.path.len(),
+                warning.max_depth
+            );
+        }
+    }
+
+    if !type_mismatches.is_empty() {
+        out.push_str("\n### Values that change type\n\n");
+        out.push_str("| Severity | File | Path | Type | Was |\n| --- | --- | --- | --- | --- |\n");
+        for warning in type_mismatches {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} ({}) |",
+                warning.severity,
+                location(&warning.file, warning.line, repo_url),
+                code(&format_path(&warning.path)),
+                describe(warning.value_type, warning.value.as_deref()),
+                describe(warning.previous_type, warning.previous_value.as_deref()),
+                location(&warning.previous_file, warning.previous_line, repo_url)
+            );
+        }
+    }
+
+    if !null_empty.is_empty() {
+        out.push_str("\n### Nulls and empty strings taken for each other\n\n");
+        out.push_str("| Severity | File | Path | Value | Was |\n| --- | --- | --- | --- | --- |\n");
+        for warning in null_empty {
+            let (now, before) = if warning.is_null {
+                ("null", "empty string")
+            } else {
+                ("empty string", "null")
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {now} | {before} ({}) |",
+                warning.severity,
+                location(&warning.file, warning.line, repo_url),
+                code(&format_path(&warning.path)),
+                location(&warning.previous_file, warning.previous_line, repo_url)
+            );
+        }
+    }
+
+    if !conflicts.is_empty() {
+        out.push_str("\n### Override files that disagree, so their order decides\n\n");
+        out.push_str(
+            "| Severity | File | Path | Value | Conflicts with |\n| --- | --- | --- | --- | --- |\n",
+        );
+        for warning in conflicts {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} ({}) |",
+                warning.severity,
+                location(&warning.file, warning.line, repo_url),
+                code(&format_path(&warning.path)),
+                code(&warning.value),
+                code(&warning.previous_value),
+                location(&warning.previous_file, warning.previous_line, repo_url)
+            );
+        }
+    }
+
+    out
+}
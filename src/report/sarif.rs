@@ -0,0 +1,238 @@
+//! SARIF 2.1.0 output for code scanning dashboards.
+
+use super::{
+    Findings, conflict_message, deep_path_message, null_empty_message, redundant_subtree_message,
+    type_mismatch_message,
+};
+use crate::{Severity, canonical_path, format_path};
+use anyhow::Result;
+use serde_json::{Value, json};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub const POINTLESS_OVERRIDE_RULE: &str = "pointless-override";
+pub const DUPLICATE_KEY_RULE: &str = "duplicate-key";
+pub const REDUNDANT_SUBTREE_RULE: &str = "redundant-subtree";
+pub const DEEP_PATH_RULE: &str = "deep-path";
+pub const TYPE_MISMATCH_RULE: &str = "type-mismatch";
+pub const NULL_EMPTY_RULE: &str = "null-empty";
+pub const CONFLICT_RULE: &str = "conflict";
+
+/// Start line/column and end line/column of a finding.
+type Region = (usize, usize, usize, usize);
+
+/// Where a finding is: its file, region and path.
+struct Location<'a> {
+    file: &'a str,
+    region: Region,
+    path: &'a [String],
+}
+
+/// The SARIF level for a severity.
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "note",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// A result, with the path as its logical location in canonical form, so
+/// tools can tell findings apart without parsing the message.
+fn result(rule_id: &str, level: &str, message: String, location: Location) -> Value {
+    let (start_line, start_column, end_line, end_column) = location.region;
+    json!({
+        "ruleId": rule_id,
+        "level": level,
+        "message": { "text": message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": location.file },
+                "region": {
+                    "startLine": start_line,
+                    "startColumn": start_column,
+                    "endLine": end_line,
+                    "endColumn": end_column,
+                },
+            },
+            "logicalLocations": [{
+                "fullyQualifiedName": canonical_path(location.path),
+                "kind": "member",
+            }],
+        }],
+    })
+}
+
+pub fn to_sarif(findings: &Findings) -> Result<String> {
+    let mut results = Vec::new();
+
+    for override_item in findings.pointless_overrides {
+        results.push(result(
+            POINTLESS_OVERRIDE_RULE,
+            level(override_item.severity),
+            format!(
+                "`{}` is set to `{}`, the same as {}:{}",
+                format_path(&override_item.path),
+                override_item.value,
+                override_item.previous_file,
+                override_item.previous_line
+            ),
+            Location {
+                file: &override_item.file,
+                region: (
+                    override_item.line,
+                    override_item.column,
+                    override_item.end_line,
+                    override_item.end_column,
+                ),
+                path: &override_item.path,
+            },
+        ));
+    }
+
+    for subtree in findings.redundant_subtrees {
+        results.push(result(
+            REDUNDANT_SUBTREE_RULE,
+            level(subtree.severity),
+            redundant_subtree_message(subtree),
+            Location {
+                file: &subtree.file,
+                region: (subtree.line, subtree.column, subtree.line, subtree.column),
+                path: &subtree.path,
+            },
+        ));
+    }
+
+    for warning in findings.warnings {
+        results.push(result(
+            DUPLICATE_KEY_RULE,
+            level(warning.severity),
+            format!(
+                "`{}` is defined twice with different values: `{}` (line {}) and `{}`",
+                format_path(&warning.path),
+                warning.first_value,
+                warning.first_line,
+                warning.second_value
+            ),
+            Location {
+                file: &warning.file,
+                region: (
+                    warning.second_line,
+                    warning.column,
+                    warning.end_line,
+                    warning.end_column,
+                ),
+                path: &warning.path,
+            },
+        ));
+    }
+
+    // The other warnings point at a single spot rather than a span
+    for warning in findings.deep_paths {
+        results.push(result(
+            DEEP_PATH_RULE,
+            level(warning.severity),
+            deep_path_message(warning),
+            Location {
+                file: &warning.file,
+                region: (warning.line, warning.column, warning.line, warning.column),
+                path: &warning.path,
+            },
+        ));
+    }
+    for warning in findings.type_mismatches {
+        results.push(result(
+            TYPE_MISMATCH_RULE,
+            level(warning.severity),
+            type_mismatch_message(warning),
+            Location {
+                file: &warning.file,
+                region: (warning.line, warning.column, warning.line, warning.column),
+                path: &warning.path,
+            },
+        ));
+    }
+    for warning in findings.null_empty {
+        results.push(result(
+            NULL_EMPTY_RULE,
+            level(warning.severity),
+            null_empty_message(warning),
+            Location {
+                file: &warning.file,
+                region: (warning.line, warning.column, warning.line, warning.column),
+                path: &warning.path,
+            },
+        ));
+    }
+    for warning in findings.conflicts {
+        results.push(result(
+            CONFLICT_RULE,
+            level(warning.severity),
+            conflict_message(warning),
+            Location {
+                file: &warning.file,
+                region: (warning.line, warning.column, warning.line, warning.column),
+                path: &warning.path,
+            },
+        ));
+    }
+
+    let log = json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": [
+                        {
+                            "id": POINTLESS_OVERRIDE_RULE,
+                            "shortDescription": {
+                                "text": "Override sets a value that is already in effect"
+                            },
+                        },
+                        {
+                            "id": REDUNDANT_SUBTREE_RULE,
+                            "shortDescription": {
+                                "text": "Subtree whose every value is already in effect"
+                            },
+                        },
+                        {
+                            "id": DUPLICATE_KEY_RULE,
+                            "shortDescription": {
+                                "text": "Key defined more than once with different values"
+                            },
+                        },
+                        {
+                            "id": DEEP_PATH_RULE,
+                            "shortDescription": {
+                                "text": "Value nested beyond the maximum depth"
+                            },
+                        },
+                        {
+                            "id": TYPE_MISMATCH_RULE,
+                            "shortDescription": {
+                                "text": "Override changes the type of a value"
+                            },
+                        },
+                        {
+                            "id": NULL_EMPTY_RULE,
+                            "shortDescription": {
+                                "text": "Null and empty string taken for each other"
+                            },
+                        },
+                        {
+                            "id": CONFLICT_RULE,
+                            "shortDescription": {
+                                "text": "Override files disagree, so their order decides"
+                            },
+                        },
+                    ],
+                },
+            },
+            "results": results,
+        }],
+    });
+    Ok(serde_json::to_string_pretty(&log)?)
+}
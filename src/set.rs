@@ -0,0 +1,163 @@
+//! Helm `--set` expressions, given on the command line rather than in a
+//! file, which make up a layer of their own above every file.
+
+use crate::{Entries, ListItem, ValueType, ValueWithLocation, render_list};
+use saphyr_parser::{Marker, ScalarStyle};
+
+/// The assignments in one expression, such as `image.tag=v2,ports[0]=80`,
+/// all on line `line` of `file`. As in Helm, `\` escapes the next character,
+/// `[N]` indexes a list and `{a,b}` is a list of scalars.
+pub(crate) fn parse(file: &str, expression: &str, line: usize) -> Result<Entries, String> {
+    let mut values = Vec::new();
+    for (offset, assignment) in split_unescaped(expression, ',') {
+        let Some((key, value)) = split_once_unescaped(assignment, '=') else {
+            return Err(format!("`{assignment}` is not of the form key=value"));
+        };
+        let (path, parents) = parse_key(key)?;
+
+        let marker =
+            |offset: usize| Marker::new(offset, line, expression[..offset].chars().count());
+        let mut location = ValueWithLocation::new(
+            String::new(),
+            file.to_string(),
+            marker(offset + key.len() + 1),
+            marker(offset + assignment.len()),
+        );
+        location.set_key(Some(marker(offset)));
+        if let Some(list) = value
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+        {
+            let items: Vec<ListItem> = split_unescaped(list, ',')
+                .into_iter()
+                .filter(|(_, item)| !item.is_empty())
+                .map(|(_, item)| ListItem::scalar(&unescape(item), ScalarStyle::Plain, None))
+                .collect();
+            location.value = render_list(&items);
+            location.items = Some(items);
+            location.value_type = ValueType::Sequence;
+        } else {
+            location.value = unescape(value);
+            location.value_type = value_type(&location.value);
+            location.is_null = location.value_type == ValueType::Null;
+        }
+        location.parents = parents;
+        values.push((path, location));
+    }
+    Ok(values)
+}
+
+/// The type Helm gives a value, which is narrower than YAML's: only `true`,
+/// `false`, `null` and plain integers are anything but strings.
+fn value_type(value: &str) -> ValueType {
+    let lowercase = value.to_ascii_lowercase();
+    if lowercase == "true" || lowercase == "false" {
+        ValueType::Bool
+    } else if lowercase == "null" {
+        ValueType::Null
+    } else if value.parse::<i64>().is_ok() && (value == "0" || !value.starts_with('0')) {
+        ValueType::Int
+    } else {
+        ValueType::String
+    }
+}
+
+/// The path a key names, and the kind of collection each segment is in:
+/// `a.b[0]` is `b`'s first element.
+fn parse_key(key: &str) -> Result<(Vec<String>, Vec<ValueType>), String> {
+    let mut path = Vec::new();
+    let mut parents = Vec::new();
+    let mut segment = String::new();
+    let mut chars = key.chars();
+    // Whether the segment so far ends in an index, so a key may not follow
+    // without a `.`
+    let mut after_index = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '.' | '[' if !after_index && segment.is_empty() => {
+                return Err(format!("`{key}` has an empty key"));
+            }
+            '.' => {
+                if !after_index {
+                    path.push(std::mem::take(&mut segment));
+                    parents.push(ValueType::Mapping);
+                }
+                after_index = false;
+            }
+            '[' => {
+                if !after_index {
+                    path.push(std::mem::take(&mut segment));
+                    parents.push(ValueType::Mapping);
+                }
+                let index: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                if index.parse::<usize>().is_err() {
+                    return Err(format!("`[{index}]` in `{key}` is not a list index"));
+                }
+                path.push(index);
+                parents.push(ValueType::Sequence);
+                after_index = true;
+            }
+            _ if after_index => return Err(format!("`{key}` needs a `.` after `]`")),
+            '\\' => segment.push(chars.next().unwrap_or('\\')),
+            _ => segment.push(c),
+        }
+    }
+    if !after_index {
+        if segment.is_empty() {
+            return Err(format!("`{key}` has an empty key"));
+        }
+        path.push(segment);
+        parents.push(ValueType::Mapping);
+    }
+    Ok((path, parents))
+}
+
+/// The pieces of `text` between unescaped `separator`s outside braces, with
+/// their byte offsets.
+fn split_unescaped(text: &str, separator: char) -> Vec<(usize, &str)> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ if c == separator && depth == 0 => {
+                pieces.push((start, &text[start..index]));
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    pieces.push((start, &text[start..]));
+    pieces
+}
+
+fn split_once_unescaped(text: &str, separator: char) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == separator => return Some((&text[..index], &text[index + c.len_utf8()..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `text` with each `\` dropped and the character after it kept as is.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
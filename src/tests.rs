@@ -0,0 +1,666 @@
+use super::*;
+
+/// An analyzer for in-memory layers, base first.
+fn analyzer(layers: &[(&str, &str)]) -> PointlessPointer {
+    PointlessPointer::from_sources(
+        layers
+            .iter()
+            .map(|(label, content)| (label.to_string(), content.to_string()))
+            .collect(),
+    )
+}
+
+/// Each value of a single layer, as `(path, value)`.
+fn values(content: &str) -> Vec<(String, String)> {
+    let layers = analyzer(&[("values.yaml", content)]).collect().unwrap();
+    layers[0]
+        .values
+        .iter()
+        .map(|(path, value, _)| (format_path(path), value.clone()))
+        .collect()
+}
+
+/// Paths of the pointless overrides, as `file:path`.
+fn pointless(analyzer: &PointlessPointer) -> Vec<String> {
+    let (overrides, _) = analyzer.analyze().unwrap();
+    overrides
+        .iter()
+        .map(|item| format!("{}:{}", item.file, format_path(&item.path)))
+        .collect()
+}
+
+#[test]
+fn shortened_list_is_not_pointless() {
+    let analyzer = analyzer(&[
+        (
+            "values.yaml",
+            "containers:\n  - name: app\n    image: nginx\n  - name: side\n    image: busybox\n",
+        ),
+        (
+            "prod.yaml",
+            "containers:\n  - name: app\n    image: nginx\n",
+        ),
+    ]);
+    assert!(pointless(&analyzer).is_empty());
+}
+
+#[test]
+fn restated_list_is_pointless() {
+    let analyzer = analyzer(&[
+        (
+            "values.yaml",
+            "containers:\n  - name: app\n    image: nginx\n",
+        ),
+        (
+            "prod.yaml",
+            "containers:\n  - name: app\n    image: nginx\n",
+        ),
+    ]);
+    assert_eq!(
+        pointless(&analyzer),
+        [
+            "prod.yaml:containers.0.name",
+            "prod.yaml:containers.0.image"
+        ]
+    );
+}
+
+#[test]
+fn unchanged_field_of_changed_list_is_not_pointless() {
+    let analyzer = analyzer(&[
+        (
+            "values.yaml",
+            "containers:\n  - name: app\n    image: nginx\n",
+        ),
+        (
+            "prod.yaml",
+            "containers:\n  - name: app\n    image: redis\n",
+        ),
+    ]);
+    assert!(pointless(&analyzer).is_empty());
+}
+
+/// Write `files` to a fresh directory for tests that need real paths.
+fn write_files(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pointless-pointer-{}-{test}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for (name, content) in files {
+        fs::write(dir.join(name), content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn dead_keys_come_from_the_base_when_defaults_are_given() {
+    let dir = write_files(
+        "dead-keys-defaults",
+        &[
+            ("defaults.yaml", "a: 1\nb: 2\n"),
+            ("values.yaml", "b: 3\nc: 4\n"),
+            ("prod.yaml", "c: 5\n"),
+        ],
+    );
+    let analyzer = PointlessPointer::new(dir.join("values.yaml"), vec![dir.join("prod.yaml")])
+        .defaults(Some(dir.join("defaults.yaml")))
+        .dead_keys(true);
+    let dead: Vec<(String, String)> = analyzer
+        .findings()
+        .unwrap()
+        .filter_map(|finding| match finding {
+            Finding::DeadKey(dead) => Some((dead.file, format_path(&dead.path))),
+            _ => None,
+        })
+        .collect();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(dead.len(), 1);
+    assert!(dead[0].0.ends_with("values.yaml"));
+    assert_eq!(dead[0].1, "b");
+}
+
+#[test]
+fn flow_mappings_give_block_paths() {
+    assert_eq!(
+        values("resources: {limits: {cpu: \"500m\", memory: 1Gi}, requests: {cpu: 1}}\n"),
+        values(
+            "resources:\n  limits:\n    cpu: \"500m\"\n    memory: 1Gi\n  requests:\n    cpu: 1\n"
+        )
+    );
+}
+
+#[test]
+fn flow_sequences_serialize_like_block_sequences() {
+    let flow = values("ports: [80, 443]\nhosts: [{name: a}, {name: b}]\n");
+    assert_eq!(
+        flow,
+        values("ports:\n  - 80\n  - 443\nhosts:\n  - name: a\n  - name: b\n")
+    );
+    assert_eq!(flow[0], ("ports".to_string(), "[80, 443]".to_string()));
+}
+
+#[test]
+fn anchored_value_equals_its_literal() {
+    let analyzer = analyzer(&[
+        ("values.yaml", "timeout: &t 30\n"),
+        ("prod.yaml", "timeout: 30\n"),
+    ]);
+    assert_eq!(pointless(&analyzer), ["prod.yaml:timeout"]);
+}
+
+#[test]
+fn restated_value_is_reported_once_per_overlay() {
+    let analyzer = analyzer(&[
+        ("values.yaml", "image:\n  tag: v1\n"),
+        ("a.yaml", "image:\n  tag: v1\n"),
+        ("b.yaml", "image:\n  tag: v1\n"),
+        ("c.yaml", "image:\n  tag: v1\n"),
+    ]);
+    let (overrides, _) = analyzer.analyze().unwrap();
+    let found: Vec<(&str, &str)> = overrides
+        .iter()
+        .map(|item| (item.file.as_str(), item.previous_file.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("a.yaml", "values.yaml"),
+            ("b.yaml", "a.yaml"),
+            ("c.yaml", "b.yaml")
+        ]
+    );
+}
+
+fn path(segments: &[&str]) -> Vec<String> {
+    segments.iter().map(|segment| segment.to_string()).collect()
+}
+
+#[test]
+fn format_path_brackets_annotation_keys() {
+    assert_eq!(
+        format_path(&path(&["metadata", "labels", "app.kubernetes.io/name"])),
+        "metadata.labels[\"app.kubernetes.io/name\"]"
+    );
+    assert_eq!(
+        format_path(&path(&[
+            "annotations",
+            "nginx.ingress.kubernetes.io/rewrite-target"
+        ])),
+        "annotations[\"nginx.ingress.kubernetes.io/rewrite-target\"]"
+    );
+    // A slash alone is unambiguous
+    assert_eq!(
+        format_path(&path(&["annotations", "example/team"])),
+        "annotations.example/team"
+    );
+}
+
+#[test]
+fn overrides_show_annotation_keys_bracketed() {
+    let analyzer = analyzer(&[
+        ("values.yaml", "labels:\n  app.kubernetes.io/name: web\n"),
+        ("prod.yaml", "labels:\n  app.kubernetes.io/name: web\n"),
+    ]);
+    assert_eq!(
+        pointless(&analyzer),
+        ["prod.yaml:labels[\"app.kubernetes.io/name\"]"]
+    );
+}
+
+#[test]
+fn numeric_and_boolean_list_items_keep_their_form() {
+    assert_eq!(
+        values("ids: [1, 2.5, \"3\"]\nflags: [true, no]\n"),
+        [
+            ("ids".to_string(), "[1, 2.5, \"3\"]".to_string()),
+            ("flags".to_string(), "[true, no]".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn numeric_and_boolean_lists_compare_by_item() {
+    let layers = [
+        (
+            "values.yaml",
+            "ids: [1, 2, 3]\nflags: [true, false]\nports: [80]\n",
+        ),
+        (
+            "prod.yaml",
+            "ids:\n  - 1\n  - 2.0\n  - 3\nflags: [yes, no]\nports: [\"80\"]\n",
+        ),
+    ];
+    assert_eq!(
+        pointless(&analyzer(&layers)),
+        ["prod.yaml:ids", "prod.yaml:flags", "prod.yaml:ports"]
+    );
+    assert!(pointless(&analyzer(&layers).strict_strings(true)).is_empty());
+}
+
+#[test]
+fn reverse_precedence_catches_base_restating_overlay() {
+    let layers = [
+        ("values.yaml", "replicas: 3\nimage: nginx\n"),
+        ("prod.yaml", "replicas: 3\n"),
+    ];
+    let (overrides, _) = analyzer(&layers)
+        .reverse_precedence(true)
+        .analyze()
+        .unwrap();
+    let found: Vec<(&str, &str)> = overrides
+        .iter()
+        .map(|item| (item.file.as_str(), item.previous_file.as_str()))
+        .collect();
+    assert_eq!(found, [("values.yaml", "prod.yaml")]);
+
+    // By default the overlay is the one restating the base
+    assert_eq!(pointless(&analyzer(&layers)), ["prod.yaml:replicas"]);
+}
+
+#[test]
+fn root_scalars_are_only_compared_when_included() {
+    let layers = [("values.yaml", "\"hello\"\n"), ("prod.yaml", "\"hello\"\n")];
+    assert!(pointless(&analyzer(&layers)).is_empty());
+    assert_eq!(
+        pointless(&analyzer(&layers).include_root_scalars(true)),
+        ["prod.yaml:(root)"]
+    );
+}
+
+#[test]
+fn byte_order_mark_is_ignored() {
+    let analyzer = analyzer(&[
+        ("values.yaml", "\u{feff}image:\n  tag: v1\nreplicas: 2\n"),
+        ("prod.yaml", "\u{feff}image:\n  tag: v1\nreplicas: 3\n"),
+    ]);
+    let (overrides, warnings) = analyzer.analyze().unwrap();
+    assert!(warnings.is_empty());
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(overrides[0].path, path(&["image", "tag"]));
+    assert_eq!((overrides[0].line, overrides[0].column), (2, 8));
+}
+
+#[test]
+fn byte_order_mark_is_ignored_when_streaming() {
+    let dir = write_files(
+        "bom",
+        &[
+            ("values.yaml", "\u{feff}image:\n  tag: v1\n"),
+            ("prod.yaml", "\u{feff}image:\n  tag: v1\n"),
+        ],
+    );
+    let (overrides, _) =
+        PointlessPointer::new(dir.join("values.yaml"), vec![dir.join("prod.yaml")])
+            .analyze()
+            .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(overrides.len(), 1);
+    assert_eq!((overrides[0].line, overrides[0].column), (2, 8));
+}
+
+#[test]
+fn json_base_with_yaml_overlay() {
+    let analyzer = analyzer(&[
+        (
+            "values.json",
+            "{\n  \"image\": {\"tag\": \"v1\"},\n  \"ports\": [{\"port\": 80}, {\"port\": 443}],\n  \"replicas\": 2\n}\n",
+        ),
+        (
+            "prod.yaml",
+            "image:\n  tag: v1\nports:\n  - port: 80\n  - port: 443\nreplicas: 3\n",
+        ),
+    ]);
+    let (overrides, _) = analyzer.analyze().unwrap();
+    let found: Vec<(String, usize)> = overrides
+        .iter()
+        .map(|item| (format_path(&item.path), item.previous_line))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("image.tag".to_string(), 2),
+            ("ports.0.port".to_string(), 3),
+            ("ports.1.port".to_string(), 3),
+        ]
+    );
+}
+
+#[test]
+fn empty_and_filled_collections() {
+    let overridden = |base: &str, overlay: &str| {
+        pointless(&analyzer(&[("values.yaml", base), ("prod.yaml", overlay)]))
+    };
+    // Both empty
+    assert_eq!(
+        overridden(
+            "annotations: {}\ntolerations: []\n",
+            "annotations: {}\ntolerations: []\n"
+        ),
+        ["prod.yaml:annotations", "prod.yaml:tolerations"]
+    );
+    // Emptied by the overlay
+    assert!(
+        overridden(
+            "annotations: {a: b}\ntolerations: [x]\n",
+            "annotations: {}\ntolerations: []\n"
+        )
+        .is_empty()
+    );
+    // Filled by the overlay
+    assert!(
+        overridden(
+            "annotations: {}\ntolerations: []\n",
+            "annotations: {a: b}\ntolerations: [x]\n"
+        )
+        .is_empty()
+    );
+    // Both filled the same
+    assert_eq!(
+        overridden(
+            "annotations: {a: b}\ntolerations: [x]\n",
+            "annotations: {a: b}\ntolerations: [x]\n"
+        ),
+        ["prod.yaml:annotations.a", "prod.yaml:tolerations"]
+    );
+}
+
+#[test]
+fn pairwise_compares_with_the_layer_below_only() {
+    // The middle layer changes the value and the top one restores the base's
+    let layers = [
+        ("values.yaml", "tag: v1\nreplicas: 2\n"),
+        ("staging.yaml", "tag: v2\n"),
+        ("prod.yaml", "tag: v1\nreplicas: 2\n"),
+    ];
+    assert_eq!(pointless(&analyzer(&layers)), ["prod.yaml:replicas"]);
+    assert!(pointless(&analyzer(&layers).pairwise(true)).is_empty());
+
+    // Restating the middle layer is pointless either way
+    let layers = [
+        ("values.yaml", "tag: v1\n"),
+        ("staging.yaml", "tag: v2\n"),
+        ("prod.yaml", "tag: v2\n"),
+    ];
+    assert_eq!(pointless(&analyzer(&layers)), ["prod.yaml:tag"]);
+    assert_eq!(
+        pointless(&analyzer(&layers).pairwise(true)),
+        ["prod.yaml:tag"]
+    );
+}
+
+#[test]
+fn quoted_numbers_match_plain_ones_unless_strings_are_strict() {
+    let layers = [
+        (
+            "values.yaml",
+            "version: 1.10\nport: 80\nratio: 0.5\nname: web\n",
+        ),
+        (
+            "prod.yaml",
+            "version: \"1.10\"\nport: '80'\nratio: \"0.50\"\nname: \"web\"\n",
+        ),
+    ];
+    assert_eq!(
+        pointless(&analyzer(&layers)),
+        [
+            "prod.yaml:version",
+            "prod.yaml:port",
+            "prod.yaml:ratio",
+            "prod.yaml:name"
+        ]
+    );
+    // Quoting that doesn't change the type still compares equal
+    assert_eq!(
+        pointless(&analyzer(&layers).strict_strings(true)),
+        ["prod.yaml:name"]
+    );
+}
+
+#[test]
+fn canonical_paths_round_trip() {
+    let paths = [
+        path(&[]),
+        path(&["image", "tag"]),
+        path(&["containers", "0", "env", "12", "value"]),
+        path(&["annotations", "app.kubernetes.io/name"]),
+        path(&["", "007", "1e3", "-1", "2nd"]),
+        path(&["env", "name=FOO", "value"]),
+        path(&["quote\"d", "back\\slash", "tab\there", "línea", "[0]"]),
+    ];
+    for path in paths {
+        let canonical = canonical_path(&path);
+        assert_eq!(
+            parse_canonical_path(&canonical).unwrap(),
+            path,
+            "{canonical}"
+        );
+    }
+}
+
+#[test]
+fn canonical_paths_name_each_segment_kind() {
+    assert_eq!(canonical_path(&path(&[])), "$");
+    assert_eq!(
+        canonical_path(&path(&["a_1", "0", "10", "007", "2nd", "a.b"])),
+        r#"$.a_1[0][10]["007"]["2nd"]["a.b"]"#
+    );
+}
+
+#[test]
+fn malformed_canonical_paths_are_rejected() {
+    for text in [
+        "",
+        "a.b",
+        "$.",
+        "$.2nd",
+        "$[007]",
+        "$[",
+        "$[1",
+        "$[\"a",
+        "$[\"a\"",
+        "$[\"a\\q\"]",
+        "$a",
+    ] {
+        assert!(parse_canonical_path(text).is_err(), "{text}");
+    }
+}
+
+/// Whether `over` restating `base` at `cpu` is pointless with `epsilon`.
+fn pointless_within(base: &str, over: &str, epsilon: f64) -> bool {
+    let analyzer = analyzer(&[
+        ("values.yaml", &format!("cpu: {base}\n")),
+        ("prod.yaml", &format!("cpu: {over}\n")),
+    ])
+    .float_epsilon(epsilon);
+    !pointless(&analyzer).is_empty()
+}
+
+#[test]
+fn float_epsilon_bounds() {
+    assert!(!pointless_within("0.5", "0.50000001", 0.0));
+    assert!(pointless_within("0.5", "0.50000001", 1e-6));
+    // Inclusive at the boundary
+    assert!(pointless_within("0.5", "0.75", 0.25));
+    assert!(!pointless_within("0.5", "0.76", 0.25));
+    assert!(pointless_within("1", "1.0000001", 1e-6));
+    // Integers on both sides are compared exactly
+    assert!(!pointless_within("1", "2", 1.0));
+}
+
+#[test]
+fn float_epsilon_leaves_nan_and_strings_alone() {
+    assert!(!pointless_within("1.0", ".nan", 1e9));
+    assert!(pointless_within(".nan", ".NaN", 0.0));
+    assert!(!pointless_within("0.5", "'0.5000001'", 1e-3));
+    let strict = analyzer(&[
+        ("values.yaml", "cpu: 0.5\n"),
+        ("prod.yaml", "cpu: 0.5000001\n"),
+    ])
+    .float_epsilon(1e-3)
+    .strict_strings(true);
+    assert!(pointless(&strict).is_empty());
+}
+
+#[test]
+fn aliases_resolve_to_anchors_of_lower_layers() {
+    let analyzer = analyzer(&[
+        ("values.yaml", "res: &res\n  cpu: 1\nname: &n web\n"),
+        (
+            "prod.yaml",
+            "# *res in a comment\nres: *res\nname: *n\nnote: \"a *res b\"\nplain: a *res b\ntext: |\n  *res\nlist: [*n, '*x']\n",
+        ),
+    ]);
+    let layers = analyzer.collect().unwrap();
+    let values: Vec<(String, &str)> = layers[1]
+        .values
+        .iter()
+        .map(|(path, value, _)| (format_path(path), value.as_str()))
+        .collect();
+    assert_eq!(
+        values,
+        [
+            ("res.cpu".to_string(), "1"),
+            ("name".to_string(), "web"),
+            ("note".to_string(), "a *res b"),
+            ("plain".to_string(), "a *res b"),
+            ("text".to_string(), "*res\n"),
+            ("list".to_string(), "[web, '*x']"),
+        ]
+    );
+}
+
+#[test]
+fn unknown_aliases_are_found_in_one_scan() {
+    let text: Vec<char> =
+        "a: &x 1\nb: *x\nc: *y\nd: [*z, \"*q\"] # *w\ne: |\n  *v\nf: g *u\n---\nh: *x\n"
+            .chars()
+            .collect();
+    let names: Vec<String> = unknown_aliases(&text)
+        .into_iter()
+        .map(|(start, name)| {
+            assert_eq!(text[start], '*');
+            name
+        })
+        .collect();
+    assert_eq!(names, ["y", "z", "x"]);
+}
+
+#[test]
+fn stdin_document_labels_do_not_depend_on_call_order() {
+    let analyzer = analyzer(&[
+        ("values.yaml", "a: 1\nb: 2\n"),
+        (STDIN_LABEL, "a: 1\n---\nb: 3\n"),
+    ])
+    .stdin_multidoc(true);
+    let expected = ["values.yaml", "<stdin>#0", "<stdin>#1"];
+    assert_eq!(analyzer.labels(), expected);
+    assert_eq!(pointless(&analyzer), ["<stdin>#0:a"]);
+    assert_eq!(analyzer.labels(), expected);
+}
+
+#[test]
+fn ignoring_every_path_keeps_findings_about_whole_files() {
+    let analyzer = analyzer(&[
+        ("values.yaml", "replicas: 3\nname: web\n"),
+        ("prod.yaml", "replicas: three\n"),
+        ("extra.yaml", "name: web\n"),
+    ])
+    .ignore_paths(&["*".to_string()])
+    .unwrap();
+    let findings: Vec<Finding> = analyzer.findings().unwrap().collect();
+    assert!(
+        matches!(&findings[..], [Finding::RedundantFile(redundant)] if redundant.file == "extra.yaml"),
+        "{findings:?}"
+    );
+    assert_eq!(analyzer.suppressed(), 2);
+}
+
+#[test]
+fn baseline_hides_every_kind_that_fails_a_run() {
+    let known = analyzer(&[
+        ("values.yaml", "replicas: 3\nname: web\n"),
+        ("prod.yaml", "replicas: three\nname: web\n"),
+    ])
+    .warn_conflicts(true);
+    let mut findings: Vec<Finding> = known.findings().unwrap().collect();
+    let baseline = baseline::Baseline::from_findings(&findings);
+    assert_eq!(baseline.len(), 2, "{findings:?}");
+    assert_eq!(baseline.apply(&mut findings), 2);
+    assert!(!findings.iter().any(Finding::is_problem), "{findings:?}");
+
+    // A new value at the same path is a new finding
+    let changed = analyzer(&[
+        ("values.yaml", "replicas: 3\n"),
+        ("prod.yaml", "replicas: four\n"),
+    ]);
+    let mut findings: Vec<Finding> = changed.findings().unwrap().collect();
+    assert_eq!(baseline.apply(&mut findings), 0);
+    assert!(
+        findings
+            .iter()
+            .any(|finding| matches!(finding, Finding::TypeMismatch(_)))
+    );
+}
+
+#[test]
+fn every_report_shows_type_changes() {
+    let analyzer = analyzer(&[
+        ("values.yaml", "replicas: 3\n"),
+        ("prod.yaml", "replicas: three\n"),
+    ]);
+    let type_mismatches: Vec<TypeMismatchWarning> = analyzer
+        .findings()
+        .unwrap()
+        .filter_map(|finding| match finding {
+            Finding::TypeMismatch(warning) => Some(warning),
+            _ => None,
+        })
+        .collect();
+    let findings = report::Findings {
+        type_mismatches: &type_mismatches,
+        ..Default::default()
+    };
+    let labels = analyzer.labels();
+    assert!(
+        report::to_sarif(&findings)
+            .unwrap()
+            .contains("\"ruleId\": \"type-mismatch\"")
+    );
+    assert!(report::to_junit(&labels, &findings).contains("failures=\"1\""));
+    assert!(
+        report::to_csv(&findings)
+            .unwrap()
+            .contains("type_mismatch,")
+    );
+    assert!(report::to_github(&findings).starts_with("::warning file=prod.yaml,line=1,"));
+    assert!(report::to_markdown(&findings, None).contains("### Values that change type"));
+    assert!(report::to_html(&labels, &findings).contains("Type change"));
+}
+
+#[test]
+fn reports_show_a_redundant_subtree_in_place_of_its_overrides() {
+    let subtree = RedundantSubtree {
+        severity: Severity::Warning,
+        file: "prod.yaml".to_string(),
+        path: path(&["image"]),
+        line: 2,
+        column: 3,
+        values: 2,
+    };
+    let findings = report::Findings {
+        redundant_subtrees: std::slice::from_ref(&subtree),
+        ..Default::default()
+    };
+    let annotations = report::to_github(&findings);
+    assert_eq!(annotations.lines().count(), 1);
+    assert!(annotations.contains("title=Redundant subtree"));
+    assert!(
+        report::to_sarif(&findings)
+            .unwrap()
+            .contains("\"ruleId\": \"redundant-subtree\"")
+    );
+    assert!(
+        report::to_csv(&findings)
+            .unwrap()
+            .contains("redundant_subtree,warning,prod.yaml,image,2,2,")
+    );
+    assert!(!report::to_markdown(&findings, None).contains("No pointless overrides found"));
+}